@@ -1,14 +1,20 @@
-use crate::{ChainSpec, DepositContract};
+use crate::{
+    ChainSpec, DepositContract, HOLESKY_BEACON_GENESIS_TIME, HOODI_BEACON_GENESIS_TIME,
+    MAINNET_BEACON_GENESIS_TIME, SEPOLIA_BEACON_GENESIS_TIME,
+};
 use alloc::{boxed::Box, vec::Vec};
-use alloy_chains::Chain;
+use alloy_chains::{Chain, NamedChain};
 use alloy_consensus::Header;
-use alloy_eips::{calc_next_block_base_fee, eip1559::BaseFeeParams, eip7840::BlobParams};
+use alloy_eips::{
+    calc_next_block_base_fee, eip1559::BaseFeeParams, eip7840::BlobParams,
+    merge::SLOT_DURATION_SECS,
+};
 use alloy_genesis::Genesis;
 use alloy_primitives::{B256, U256};
 use core::fmt::{Debug, Display};
 use reth_ethereum_forks::EthereumHardforks;
 use reth_network_peers::NodeRecord;
-use reth_primitives_traits::{AlloyBlockHeader, BlockHeader};
+use reth_primitives_traits::{AlloyBlockHeader, BlockHeader, SlotConfig};
 
 /// Trait representing type configuring a chain spec.
 #[auto_impl::auto_impl(&, Arc)]
@@ -67,6 +73,26 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
     /// Returns the final total difficulty if the Paris hardfork is known.
     fn final_paris_total_difficulty(&self) -> Option<U256>;
 
+    /// Returns the parameters of the beacon chain driving this chain, if known.
+    ///
+    /// For chains other than the public Ethereum networks, the beacon chain is assumed to start
+    /// with the genesis block if the chain is merged at genesis.
+    fn slot_config(&self) -> Option<SlotConfig> {
+        let genesis_time = match self.chain().named() {
+            Some(NamedChain::Mainnet) => MAINNET_BEACON_GENESIS_TIME,
+            Some(NamedChain::Sepolia) => SEPOLIA_BEACON_GENESIS_TIME,
+            Some(NamedChain::Holesky) => HOLESKY_BEACON_GENESIS_TIME,
+            Some(NamedChain::Hoodi) => HOODI_BEACON_GENESIS_TIME,
+            _ if !self.is_optimism() &&
+                self.genesis().config.terminal_total_difficulty == Some(U256::ZERO) =>
+            {
+                self.genesis().timestamp
+            }
+            _ => return None,
+        };
+        Some(SlotConfig::new(genesis_time, SLOT_DURATION_SECS))
+    }
+
     /// See [`calc_next_block_base_fee`].
     fn next_block_base_fee(&self, parent: &Self::Header, target_timestamp: u64) -> Option<u64> {
         Some(calc_next_block_base_fee(
@@ -14,3 +14,15 @@ pub(crate) const MAINNET_DEPOSIT_CONTRACT: DepositContract = DepositContract::ne
     11052984,
     b256!("0x649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
 );
+
+/// Timestamp of the mainnet beacon chain genesis.
+pub const MAINNET_BEACON_GENESIS_TIME: u64 = 1606824023;
+
+/// Timestamp of the Sepolia beacon chain genesis.
+pub const SEPOLIA_BEACON_GENESIS_TIME: u64 = 1655733600;
+
+/// Timestamp of the Holesky beacon chain genesis.
+pub const HOLESKY_BEACON_GENESIS_TIME: u64 = 1695902400;
+
+/// Timestamp of the Hoodi beacon chain genesis.
+pub const HOODI_BEACON_GENESIS_TIME: u64 = 1742213400;
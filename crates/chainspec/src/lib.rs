@@ -16,6 +16,9 @@ mod constants;
 pub use constants::*;
 
 mod api;
+/// Contract code size limits.
+mod limits;
+pub use limits::{max_code_size, max_initcode_size};
 /// The chain info module.
 mod info;
 /// The chain spec module.
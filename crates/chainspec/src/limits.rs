@@ -0,0 +1,54 @@
+use crate::{EthereumHardfork, EthereumHardforks};
+use alloy_consensus::BlockHeader;
+use alloy_evm::revm::primitives::{eip170::MAX_CODE_SIZE, eip3860::MAX_INITCODE_SIZE};
+use reth_primitives_traits::{HeaderTy, NodePrimitives};
+
+/// Returns the maximum size of deployed contract code that is active at the given
+/// [`NodePrimitives`] block header, see [EIP-170](https://eips.ethereum.org/EIPS/eip-170).
+///
+/// Returns `None` if the code size is unlimited, i.e. before the Spurious Dragon hardfork.
+pub fn max_code_size<N: NodePrimitives>(
+    chain_spec: impl EthereumHardforks,
+    header: &HeaderTy<N>,
+) -> Option<usize> {
+    chain_spec
+        .is_ethereum_fork_active_at_block(EthereumHardfork::SpuriousDragon, header.number())
+        .then_some(MAX_CODE_SIZE)
+}
+
+/// Returns the maximum size of contract creation code that is active at the given
+/// [`NodePrimitives`] block header, see [EIP-3860](https://eips.ethereum.org/EIPS/eip-3860).
+///
+/// Returns `None` if the init code size is unlimited, i.e. before the Shanghai hardfork.
+pub fn max_initcode_size<N: NodePrimitives>(
+    chain_spec: impl EthereumHardforks,
+    header: &HeaderTy<N>,
+) -> Option<usize> {
+    chain_spec.is_shanghai_active_at_timestamp(header.timestamp()).then_some(MAX_INITCODE_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAINNET;
+    use alloy_consensus::Header;
+    use reth_ethereum_primitives::EthPrimitives;
+
+    #[test]
+    fn test_code_size_limits() {
+        // before spurious dragon
+        let frontier = Header { number: 1, timestamp: 1438269988, ..Default::default() };
+        assert_eq!(max_code_size::<EthPrimitives>(&*MAINNET, &frontier), None);
+        assert_eq!(max_initcode_size::<EthPrimitives>(&*MAINNET, &frontier), None);
+
+        // the last block before shanghai
+        let paris = Header { number: 17034869, timestamp: 1681338443, ..Default::default() };
+        assert_eq!(max_code_size::<EthPrimitives>(&*MAINNET, &paris), Some(24576));
+        assert_eq!(max_initcode_size::<EthPrimitives>(&*MAINNET, &paris), None);
+
+        // the first shanghai block
+        let shanghai = Header { number: 17034870, timestamp: 1681338455, ..Default::default() };
+        assert_eq!(max_code_size::<EthPrimitives>(&*MAINNET, &shanghai), Some(24576));
+        assert_eq!(max_initcode_size::<EthPrimitives>(&*MAINNET, &shanghai), Some(49152));
+    }
+}
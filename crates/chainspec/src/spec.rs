@@ -1031,6 +1031,7 @@ pub fn test_fork_ids(spec: &ChainSpec, cases: &[(Head, ForkId)]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{HOODI_BEACON_GENESIS_TIME, MAINNET_BEACON_GENESIS_TIME};
     use alloy_chains::Chain;
     use alloy_consensus::constants::ETH_TO_WEI;
     use alloy_eips::{eip4844::BLOB_TX_MIN_BLOB_GASPRICE, eip7840::BlobParams};
@@ -1040,6 +1041,7 @@ mod tests {
     use alloy_trie::{TrieAccount, EMPTY_ROOT_HASH};
     use core::ops::Deref;
     use reth_ethereum_forks::{ForkCondition, ForkHash, ForkId, Head};
+    use reth_primitives_traits::SlotConfig;
     use std::{collections::HashMap, str::FromStr};
 
     fn test_hardfork_fork_ids(spec: &ChainSpec, cases: &[(EthereumHardfork, ForkId)]) {
@@ -2540,4 +2542,29 @@ Post-merge hard forks (timestamp based):
         };
         assert_eq!(hardfork_params, expected);
     }
+
+    #[test]
+    fn slot_config() {
+        assert_eq!(MAINNET.slot_config(), Some(SlotConfig::new(MAINNET_BEACON_GENESIS_TIME, 12)));
+        assert_eq!(HOODI.slot_config(), Some(SlotConfig::new(HOODI_BEACON_GENESIS_TIME, 12)));
+
+        // chains merged at genesis start the beacon chain with the genesis block
+        let genesis = Genesis {
+            config: ChainConfig {
+                chain_id: 1337,
+                terminal_total_difficulty: Some(U256::ZERO),
+                ..Default::default()
+            },
+            timestamp: 1698688670,
+            ..Default::default()
+        };
+        assert_eq!(ChainSpec::from(genesis).slot_config(), Some(SlotConfig::new(1698688670, 12)));
+
+        // chains merged later have no known beacon chain genesis
+        let genesis = Genesis {
+            config: ChainConfig { chain_id: 1337, ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(ChainSpec::from(genesis).slot_config(), None);
+    }
 }
@@ -14,8 +14,9 @@ extern crate alloc;
 use alloy_consensus::BlockHeader;
 use reth_errors::ConsensusError;
 use reth_payload_primitives::{
-    EngineApiMessageVersion, EngineObjectValidationError, InvalidPayloadAttributesError,
-    NewPayloadError, PayloadAttributes, PayloadOrAttributes, PayloadTypes,
+    validate_withdrawal_indices, EngineApiMessageVersion, EngineObjectValidationError,
+    InvalidPayloadAttributesError, NewPayloadError, PayloadAttributes, PayloadOrAttributes,
+    PayloadTypes,
 };
 use reth_primitives_traits::{Block, RecoveredBlock};
 use reth_trie_common::HashedPostState;
@@ -169,6 +170,9 @@ pub trait EngineValidator<Types: PayloadTypes>:
     ///   > of a block referenced by forkchoiceState.headBlockHash.
     ///
     /// See also [engine api spec](https://github.com/ethereum/execution-apis/tree/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine)
+    ///
+    /// It also enforces that the indices of the withdrawals are strictly increasing, see
+    /// [`validate_withdrawal_indices`].
     fn validate_payload_attributes_against_header(
         &self,
         attr: &<Types as PayloadTypes>::PayloadAttributes,
@@ -177,6 +181,9 @@ pub trait EngineValidator<Types: PayloadTypes>:
         if attr.timestamp() <= header.timestamp() {
             return Err(InvalidPayloadAttributesError::InvalidTimestamp);
         }
+        if let Some(withdrawals) = attr.withdrawals() {
+            validate_withdrawal_indices(withdrawals)?;
+        }
         Ok(())
     }
 }
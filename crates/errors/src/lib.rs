@@ -15,7 +15,7 @@ mod error;
 pub use error::{RethError, RethResult};
 
 pub use reth_consensus::ConsensusError;
-pub use reth_execution_errors::{BlockExecutionError, BlockValidationError};
+pub use reth_execution_errors::{BlockExecutionError, BlockStateRootError, BlockValidationError};
 pub use reth_storage_errors::{
     db::DatabaseError,
     provider::{ProviderError, ProviderResult},
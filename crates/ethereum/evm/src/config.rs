@@ -1,7 +1,14 @@
-use alloy_consensus::Header;
+use alloy_consensus::{BlockHeader, Header};
+use alloy_primitives::U256;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_forks::{EthereumHardfork, Hardforks};
-use revm::primitives::hardfork::SpecId;
+use reth_evm::EvmEnv;
+use reth_primitives_traits::{HeaderTy, NodePrimitives};
+use revm::{
+    context::{BlockEnv, CfgEnv},
+    context_interface::block::BlobExcessGasAndPrice,
+    primitives::hardfork::SpecId,
+};
 
 /// Map the latest active hardfork at the given header to a revm [`SpecId`].
 pub fn revm_spec<C>(chain_spec: &C, header: &Header) -> SpecId
@@ -95,6 +102,49 @@ where
     }
 }
 
+/// Builds the [`EvmEnv`] for executing the block with the given [`NodePrimitives`] header on the
+/// given chain.
+///
+/// The spec id and blob parameters are derived from the hardforks that are active at the header,
+/// the blob base fee from its excess blob gas.
+pub fn evm_env_from_header<N, C>(header: &HeaderTy<N>, chain_spec: &C) -> EvmEnv
+where
+    N: NodePrimitives,
+    C: EthereumHardforks + EthChainSpec + Hardforks,
+{
+    let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp());
+    let spec =
+        revm_spec_by_timestamp_and_block_number(chain_spec, header.timestamp(), header.number());
+
+    // configure evm env based on parent block
+    let mut cfg_env = CfgEnv::new().with_chain_id(chain_spec.chain().id()).with_spec(spec);
+
+    if let Some(blob_params) = &blob_params {
+        cfg_env.set_max_blobs_per_tx(blob_params.max_blobs_per_tx);
+    }
+
+    // derive the EIP-4844 blob fees from the header's `excess_blob_gas` and the current
+    // blobparams
+    let blob_excess_gas_and_price =
+        header.excess_blob_gas().zip(blob_params).map(|(excess_blob_gas, params)| {
+            let blob_gasprice = params.calc_blob_fee(excess_blob_gas);
+            BlobExcessGasAndPrice { excess_blob_gas, blob_gasprice }
+        });
+
+    let block_env = BlockEnv {
+        number: U256::from(header.number()),
+        beneficiary: header.beneficiary(),
+        timestamp: U256::from(header.timestamp()),
+        difficulty: if spec >= SpecId::MERGE { U256::ZERO } else { header.difficulty() },
+        prevrandao: if spec >= SpecId::MERGE { header.mix_hash() } else { None },
+        gas_limit: header.gas_limit(),
+        basefee: header.base_fee_per_gas().unwrap_or_default(),
+        blob_excess_gas_and_price,
+    };
+
+    EvmEnv { cfg_env, block_env }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,4 +307,45 @@ mod tests {
             SpecId::FRONTIER
         );
     }
+
+    #[test]
+    fn test_evm_env_from_header() {
+        use alloy_eips::eip7840::BlobParams;
+        use alloy_primitives::{Address, B256};
+        use reth_ethereum_primitives::EthPrimitives;
+
+        // the first cancun block on mainnet
+        let header = Header {
+            number: 19426587,
+            timestamp: 1710338135,
+            beneficiary: Address::with_last_byte(0xbe),
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            mix_hash: B256::with_last_byte(0x42),
+            difficulty: U256::from(1),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(0),
+            ..Default::default()
+        };
+        let EvmEnv { cfg_env, block_env } =
+            evm_env_from_header::<EthPrimitives, _>(&header, &*MAINNET);
+
+        assert_eq!(cfg_env.spec, SpecId::CANCUN);
+        assert_eq!(cfg_env.chain_id, 1);
+        assert_eq!(block_env.number, U256::from(header.number));
+        assert_eq!(block_env.timestamp, U256::from(header.timestamp));
+        assert_eq!(block_env.beneficiary, header.beneficiary);
+        assert_eq!(block_env.gas_limit, header.gas_limit);
+        assert_eq!(block_env.basefee, 7);
+        // post-merge the difficulty is replaced by prevrandao
+        assert_eq!(block_env.difficulty, U256::ZERO);
+        assert_eq!(block_env.prevrandao, Some(header.mix_hash));
+        assert_eq!(
+            block_env.blob_excess_gas_and_price,
+            Some(BlobExcessGasAndPrice {
+                excess_blob_gas: 0,
+                blob_gasprice: BlobParams::cancun().calc_blob_fee(0)
+            })
+        );
+    }
 }
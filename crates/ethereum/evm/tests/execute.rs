@@ -9,21 +9,33 @@ use alloy_eips::{
     eip7685::EMPTY_REQUESTS_HASH,
 };
 use alloy_evm::block::BlockValidationError;
-use alloy_primitives::{b256, fixed_bytes, keccak256, Bytes, TxKind, B256, U256};
+use alloy_primitives::{
+    b256, fixed_bytes, keccak256, Address, Bytes, StorageKey, StorageValue, TxKind, B256, U256,
+};
 use reth_chainspec::{ChainSpecBuilder, EthereumHardfork, ForkCondition, MAINNET};
 use reth_ethereum_primitives::{Block, BlockBody, Transaction};
 use reth_evm::{
-    execute::{BasicBlockExecutor, Executor},
-    ConfigureEvm,
+    execute::{touched_accounts, BasicBlockExecutor, BlockBuilder, Executor},
+    ConfigureEvm, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
 use reth_execution_types::BlockExecutionResult;
+use reth_payload_primitives::PayloadBuilderError;
 use reth_primitives_traits::{
-    crypto::secp256k1::public_key_to_address, Block as _, RecoveredBlock,
+    crypto::secp256k1::public_key_to_address, Account, Block as _, RecoveredBlock, SealedHeader,
+};
+use reth_storage_api::{
+    errors::provider::{ProviderError, ProviderResult},
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateRootProvider, StorageRootProvider,
 };
 use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+use reth_trie_common::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
 use revm::{
-    database::{CacheDB, EmptyDB, TransitionState},
+    database::{BundleState, CacheDB, EmptyDB, State, TransitionState},
     primitives::address,
     state::{AccountInfo, Bytecode, EvmState},
     Database,
@@ -826,3 +838,211 @@ fn test_balance_increment_not_duplicated() {
         );
     }
 }
+
+#[test]
+fn touched_accounts_include_dependent_transactions() {
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::from(&*MAINNET)
+            .shanghai_activated()
+            .cancun_activated()
+            .prague_activated()
+            .build(),
+    );
+
+    let sender_key_pair = generators::generate_key(&mut generators::rng());
+    let sender = public_key_to_address(sender_key_pair.public_key());
+    let recipient_key_pair = generators::generate_key(&mut generators::rng());
+    let recipient = public_key_to_address(recipient_key_pair.public_key());
+    // loads slot 1 and stops
+    let contract = address!("0x00000000000000000000000000000000000000cc");
+    let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x01, 0x54, 0x50, 0x00]));
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        sender,
+        AccountInfo { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+    );
+    db.insert_account_info(
+        contract,
+        AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+    );
+
+    let header = Header {
+        timestamp: 1,
+        number: 1,
+        gas_limit: 1_000_000,
+        excess_blob_gas: Some(0),
+        parent_beacon_block_root: Some(B256::random()),
+        ..Header::default()
+    };
+    let transfer = |nonce, to, value| TxLegacy {
+        chain_id: Some(chain_spec.chain.id()),
+        nonce,
+        gas_price: 0,
+        gas_limit: 100_000,
+        to: TxKind::Call(to),
+        value: U256::from(value),
+        input: Bytes::new(),
+    };
+    // the recipient can only call the contract with the funds of the first transaction
+    let transactions = vec![
+        sign_tx_with_key_pair(sender_key_pair, Transaction::Legacy(transfer(0, recipient, 1000))),
+        sign_tx_with_key_pair(recipient_key_pair, Transaction::Legacy(transfer(0, contract, 1))),
+    ];
+    let block = Block { header, body: BlockBody { transactions, ..Default::default() } }
+        .try_into_recovered()
+        .unwrap();
+
+    let touched = touched_accounts(&EthEvmConfig::new(chain_spec), db, &block).unwrap();
+
+    assert!(touched.contains_account(&sender));
+    assert!(touched.contains_account(&recipient));
+    assert!(touched.contains_slot(&contract, &B256::with_last_byte(1)));
+    assert!(!touched.contains_slot(&contract, &B256::ZERO));
+}
+
+/// An empty state whose state root can't be computed.
+struct FailingStateRootProvider;
+
+impl AccountReader for FailingStateRootProvider {
+    fn basic_account(&self, _address: &Address) -> ProviderResult<Option<Account>> {
+        Ok(None)
+    }
+}
+
+impl BlockHashReader for FailingStateRootProvider {
+    fn block_hash(&self, _number: u64) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+
+    fn canonical_hashes_range(&self, _start: u64, _end: u64) -> ProviderResult<Vec<B256>> {
+        Ok(Vec::new())
+    }
+}
+
+impl BytecodeReader for FailingStateRootProvider {
+    fn bytecode_by_hash(
+        &self,
+        _code_hash: &B256,
+    ) -> ProviderResult<Option<reth_primitives_traits::Bytecode>> {
+        Ok(None)
+    }
+}
+
+impl StateRootProvider for FailingStateRootProvider {
+    fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_from_nodes(&self, _input: TrieInput) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        _state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        _input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StorageRootProvider for FailingStateRootProvider {
+    fn storage_root(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn storage_proof(
+        &self,
+        _address: Address,
+        slot: B256,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        Ok(StorageProof::new(slot))
+    }
+
+    fn storage_multiproof(
+        &self,
+        _address: Address,
+        _slots: &[B256],
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        Ok(StorageMultiProof::empty())
+    }
+}
+
+impl StateProofProvider for FailingStateRootProvider {
+    fn proof(
+        &self,
+        _input: TrieInput,
+        address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        Ok(AccountProof::new(address))
+    }
+
+    fn multiproof(
+        &self,
+        _input: TrieInput,
+        _targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        Ok(MultiProof::default())
+    }
+
+    fn witness(&self, _input: TrieInput, _target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
+}
+
+impl HashedPostStateProvider for FailingStateRootProvider {
+    fn hashed_post_state(&self, _bundle_state: &BundleState) -> HashedPostState {
+        HashedPostState::default()
+    }
+}
+
+impl StateProvider for FailingStateRootProvider {
+    fn storage(
+        &self,
+        _account: Address,
+        _storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        Ok(None)
+    }
+}
+
+#[test]
+fn state_root_failure_is_payload_state_root_error() {
+    let evm_config = EthEvmConfig::mainnet();
+    let mut state = State::builder()
+        .with_database(CacheDB::new(EmptyDB::default()))
+        .with_bundle_update()
+        .build();
+    let parent = SealedHeader::seal_slow(Header { gas_limit: 1_000_000, ..Header::default() });
+    let attributes = NextBlockEnvAttributes {
+        timestamp: 1,
+        suggested_fee_recipient: Address::ZERO,
+        prev_randao: B256::ZERO,
+        gas_limit: 1_000_000,
+        parent_beacon_block_root: None,
+        withdrawals: None,
+    };
+
+    let mut builder = evm_config.builder_for_next_block(&mut state, &parent, attributes).unwrap();
+    builder.apply_pre_execution_changes().unwrap();
+    let err = builder.finish(FailingStateRootProvider).unwrap_err();
+
+    assert!(matches!(
+        PayloadBuilderError::from(err),
+        PayloadBuilderError::StateRootError(ProviderError::UnsupportedProvider)
+    ));
+}
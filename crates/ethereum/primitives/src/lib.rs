@@ -55,7 +55,197 @@ pub struct EthPrimitives;
 impl reth_primitives_traits::NodePrimitives for EthPrimitives {
     type Block = crate::Block;
     type BlockHeader = alloy_consensus::Header;
+    type OmmerHeader = alloy_consensus::Header;
     type BlockBody = crate::BlockBody;
     type SignedTx = crate::TransactionSigned;
     type Receipt = crate::Receipt;
 }
+
+impl reth_primitives_traits::PooledNodePrimitives for EthPrimitives {
+    type PooledTx = crate::PooledTransactionVariant;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives_traits::{header_slot, header_timestamp_for_slot, SlotConfig};
+
+    #[test]
+    fn test_roundtrip_header_slot() {
+        // the mainnet beacon chain
+        let config = SlotConfig::new(1606824023, 12);
+        // genesis, merge block, first slots of the shanghai and cancun epochs
+        for (slot, timestamp) in
+            [(0, 1606824023), (4700013, 1663224179), (6209536, 1681338455), (8626176, 1710338135)]
+        {
+            let header = alloy_consensus::Header { timestamp, ..Default::default() };
+            assert_eq!(header_slot::<EthPrimitives>(&header, &config), Some(slot));
+            assert_eq!(header_timestamp_for_slot::<EthPrimitives>(slot, &config), Some(timestamp));
+        }
+
+        // timestamps within a slot map to the slot
+        let header = alloy_consensus::Header { timestamp: 1663224179 + 11, ..Default::default() };
+        assert_eq!(header_slot::<EthPrimitives>(&header, &config), Some(4700013));
+
+        // blocks before the beacon chain genesis have no slot
+        let header = alloy_consensus::Header { timestamp: 1438269973, ..Default::default() };
+        assert_eq!(header_slot::<EthPrimitives>(&header, &config), None);
+
+        // slots too far in the future have no timestamp
+        assert_eq!(header_timestamp_for_slot::<EthPrimitives>(u64::MAX, &config), None);
+    }
+
+    #[test]
+    fn test_primitives_coherent() {
+        const _: () = reth_primitives_traits::assert_coherent::<EthPrimitives>();
+    }
+
+    #[test]
+    fn test_empty_header() {
+        use reth_primitives_traits::NodePrimitives;
+
+        let header = EthPrimitives::empty_header();
+        assert_eq!(header, alloy_consensus::Header::default());
+        assert_eq!(header_slot::<EthPrimitives>(&header, &SlotConfig::new(1606824023, 12)), None);
+    }
+
+    #[test]
+    fn test_reward_recipient() {
+        use alloy_hardforks::{EthereumChainHardforks, EthereumHardfork, EthereumHardforks};
+        use alloy_primitives::Address;
+        use reth_primitives_traits::NodePrimitives;
+
+        const REWARD_CONTRACT: Address = Address::with_last_byte(0xcc);
+
+        /// Credits the block rewards to a contract once Shanghai is active.
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        struct ContractRewardPrimitives;
+
+        impl NodePrimitives for ContractRewardPrimitives {
+            type Block = crate::Block;
+            type BlockHeader = alloy_consensus::Header;
+            type OmmerHeader = alloy_consensus::Header;
+            type BlockBody = crate::BlockBody;
+            type SignedTx = crate::TransactionSigned;
+            type Receipt = crate::Receipt;
+
+            fn reward_recipient<C>(header: &Self::BlockHeader, chain_spec: C) -> Address
+            where
+                C: EthereumHardforks,
+            {
+                if chain_spec.is_shanghai_active_at_timestamp(header.timestamp) {
+                    REWARD_CONTRACT
+                } else {
+                    header.beneficiary
+                }
+            }
+        }
+
+        let chain_spec = EthereumChainHardforks::mainnet();
+        let shanghai = EthereumHardfork::Shanghai.mainnet_activation_timestamp().unwrap();
+        let header = alloy_consensus::Header {
+            beneficiary: Address::with_last_byte(0xbb),
+            timestamp: shanghai - 1,
+            ..Default::default()
+        };
+        assert_eq!(EthPrimitives::reward_recipient(&header, &chain_spec), header.beneficiary);
+        assert_eq!(
+            ContractRewardPrimitives::reward_recipient(&header, &chain_spec),
+            header.beneficiary
+        );
+
+        let header = alloy_consensus::Header { timestamp: shanghai, ..header };
+        assert_eq!(EthPrimitives::reward_recipient(&header, &chain_spec), header.beneficiary);
+        assert_eq!(
+            ContractRewardPrimitives::reward_recipient(&header, &chain_spec),
+            REWARD_CONTRACT
+        );
+    }
+
+    #[test]
+    fn test_senders_and_kinds() {
+        use alloy_consensus::{Signed, TxEip1559, TxEip4844, TxLegacy};
+        use alloy_primitives::{Address, Signature, TxKind};
+        use reth_primitives_traits::{block::RecoveredBlock, senders_and_kinds};
+
+        let signature = Signature::test_signature();
+        let to = Address::with_last_byte(0xcc);
+        let transactions = vec![
+            TransactionSigned::Legacy(Signed::new_unhashed(
+                TxLegacy { to: TxKind::Create, ..Default::default() },
+                signature,
+            )),
+            TransactionSigned::Eip1559(Signed::new_unhashed(
+                TxEip1559 { to: TxKind::Call(to), ..Default::default() },
+                signature,
+            )),
+            TransactionSigned::Eip4844(Signed::new_unhashed(
+                TxEip4844 { to, ..Default::default() },
+                signature,
+            )),
+        ];
+        let senders = (1..=3).map(Address::with_last_byte).collect::<Vec<_>>();
+        let block = RecoveredBlock::new_unhashed(
+            Block { body: BlockBody { transactions, ..Default::default() }, ..Default::default() },
+            senders.clone(),
+        );
+
+        assert_eq!(
+            senders_and_kinds::<EthPrimitives>(&block).collect::<Vec<_>>(),
+            vec![
+                (senders[0], TxKind::Create),
+                (senders[1], TxKind::Call(to)),
+                (senders[2], TxKind::Call(to)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calculate_withdrawals_root() {
+        use alloy_eips::eip4895::{Withdrawal, Withdrawals};
+        use alloy_primitives::address;
+        use reth_primitives_traits::{BlockBody, BodyTy};
+
+        let root = |withdrawals| {
+            BlockBody::calculate_withdrawals_root(&BodyTy::<EthPrimitives> {
+                withdrawals,
+                ..Default::default()
+            })
+        };
+
+        // pre-shanghai
+        assert_eq!(root(None), None);
+
+        // post-shanghai without withdrawals
+        assert_eq!(root(Some(Withdrawals::default())), Some(alloy_consensus::EMPTY_ROOT_HASH));
+
+        let withdrawals = (0..16u64)
+            .map(|index| Withdrawal {
+                index,
+                validator_index: 0,
+                address: address!("0x00000000000000000000000000000000000000bb"),
+                amount: 1,
+            })
+            .collect::<Vec<_>>();
+        let non_empty = root(Some(Withdrawals::new(withdrawals.clone())));
+        assert_eq!(
+            non_empty,
+            Some(alloy_consensus::proofs::calculate_withdrawals_root(&withdrawals))
+        );
+        assert_ne!(non_empty, Some(alloy_consensus::EMPTY_ROOT_HASH));
+    }
+
+    #[cfg(feature = "reth-codec")]
+    proptest::proptest! {
+        #[test]
+        fn test_roundtrip_compact_header(
+            header in proptest_arbitrary_interop::arb::<alloy_consensus::Header>()
+        ) {
+            let encoded = reth_primitives_traits::compact_encode_header::<EthPrimitives>(&header);
+            assert_eq!(
+                reth_primitives_traits::compact_decode_header::<EthPrimitives>(&encoded),
+                header
+            );
+        }
+    }
+}
@@ -461,8 +461,9 @@ mod tests {
     };
     use alloy_rlp::Decodable;
     use reth_codecs::Compact;
-    use reth_primitives_traits::proofs::{
-        calculate_receipt_root, calculate_transaction_root, calculate_withdrawals_root,
+    use reth_primitives_traits::{
+        proofs::{calculate_receipt_root, calculate_transaction_root, calculate_withdrawals_root},
+        NodePrimitives,
     };
 
     /// Ethereum full block.
@@ -606,6 +607,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_empty_block_logs_bloom() {
+        let receipts: Vec<Receipt> = Vec::new();
+
+        let bloom = alloy_primitives::logs_bloom(receipts.iter().flat_map(|r| r.logs.iter()));
+        assert_eq!(bloom, <Receipt as reth_primitives_traits::Receipt>::EMPTY_LOGS_BLOOM);
+        assert_eq!(bloom, <crate::EthPrimitives as NodePrimitives>::EMPTY_LOGS_BLOOM);
+
+        let root = Receipt::calculate_receipt_root_no_memo(&receipts);
+        assert_eq!(root, <crate::EthPrimitives as NodePrimitives>::EMPTY_RECEIPTS_ROOT);
+
+        assert!(reth_primitives_traits::Receipt::has_no_logs(&Receipt::default()));
+    }
+
     #[test]
     fn check_transaction_root() {
         let data = &hex!(
@@ -671,4 +686,29 @@ mod tests {
             b256!("0xfe70ae4a136d98944951b2123859698d59ad251a381abc9960fa81cae3d0d4a0")
         );
     }
+
+    #[test]
+    fn test_receipts_gas_used() {
+        use reth_primitives_traits::{receipts_gas_used, CumulativeGasError};
+
+        let receipt = |cumulative_gas_used| Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used,
+            logs: vec![],
+        };
+
+        let receipts = vec![receipt(21_000), receipt(71_000), receipt(71_000), receipt(92_500)];
+        assert_eq!(
+            receipts_gas_used::<crate::EthPrimitives>(&receipts),
+            Ok(vec![21_000, 50_000, 0, 21_500])
+        );
+        assert_eq!(receipts_gas_used::<crate::EthPrimitives>(&[]), Ok(vec![]));
+
+        let corrupt = vec![receipt(21_000), receipt(71_000), receipt(50_000)];
+        assert_eq!(
+            receipts_gas_used::<crate::EthPrimitives>(&corrupt),
+            Err(CumulativeGasError { index: 2, cumulative_gas_used: 50_000, previous: 71_000 })
+        );
+    }
 }
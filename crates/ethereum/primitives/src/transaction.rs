@@ -667,10 +667,148 @@ impl SignedTransaction for TransactionSigned {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_consensus::EthereumTxEnvelope;
+    use alloy_consensus::{crypto::secp256k1::sign_message, EthereumTxEnvelope};
     use proptest::proptest;
     use proptest_arbitrary_interop::arb;
     use reth_codecs::Compact;
+    use reth_primitives_traits::{
+        authorization_list, blob_versioned_hashes, calldata_bytes,
+        transaction::{error::TxHeaderError, recover::recover_senders_par},
+        tx_fits_header,
+    };
+
+    #[test]
+    fn test_recover_senders_par() {
+        let mut txs = (1..=16u8)
+            .map(|nonce| {
+                let tx = TxLegacy { nonce: nonce.into(), ..Default::default() };
+                let signature = sign_message(B256::with_last_byte(nonce), tx.signature_hash())
+                    .expect("valid secret");
+                crate::TransactionSigned::Legacy(tx.into_signed(signature))
+            })
+            .collect::<Vec<_>>();
+
+        let expected = txs.iter().map(|tx| tx.recover_signer().unwrap()).collect::<Vec<_>>();
+        assert_eq!(recover_senders_par::<crate::EthPrimitives>(&txs).unwrap(), expected);
+
+        let invalid = TxLegacy { nonce: 7, ..Default::default() }.into_signed(Signature::new(
+            U256::ZERO,
+            U256::ZERO,
+            false,
+        ));
+        txs[11] = crate::TransactionSigned::Legacy(invalid);
+        let err = recover_senders_par::<crate::EthPrimitives>(&txs).unwrap_err();
+        assert_eq!(err.index, 11);
+    }
+
+    #[test]
+    fn test_calldata_bytes() {
+        let signature = Signature::test_signature();
+        let txs = |input: Bytes| {
+            [
+                crate::TransactionSigned::Legacy(
+                    TxLegacy { input: input.clone(), ..Default::default() }.into_signed(signature),
+                ),
+                crate::TransactionSigned::Eip2930(
+                    TxEip2930 { input: input.clone(), ..Default::default() }.into_signed(signature),
+                ),
+                crate::TransactionSigned::Eip1559(
+                    TxEip1559 { input: input.clone(), ..Default::default() }.into_signed(signature),
+                ),
+                crate::TransactionSigned::Eip4844(
+                    TxEip4844 { input: input.clone(), ..Default::default() }.into_signed(signature),
+                ),
+                crate::TransactionSigned::Eip7702(
+                    TxEip7702 { input, ..Default::default() }.into_signed(signature),
+                ),
+            ]
+        };
+
+        for (input, expected) in [
+            (Bytes::new(), (0, 0)),
+            (Bytes::from_static(&[0; 4]), (4, 0)),
+            (Bytes::from_static(&[0xa9, 0x05, 0x00, 0x00, 0x01]), (2, 3)),
+        ] {
+            for tx in txs(input.clone()) {
+                assert_eq!(calldata_bytes::<crate::EthPrimitives>(&tx), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_authorization_list() {
+        let signature = Signature::test_signature();
+        let authorizations = (0..2u64)
+            .map(|nonce| {
+                alloy_eips::eip7702::Authorization {
+                    chain_id: U256::from(1),
+                    address: Address::with_last_byte(0xde),
+                    nonce,
+                }
+                .into_signed(signature)
+            })
+            .collect::<Vec<_>>();
+        let tx = crate::TransactionSigned::Eip7702(
+            TxEip7702 { authorization_list: authorizations.clone(), ..Default::default() }
+                .into_signed(signature),
+        );
+        assert_eq!(authorization_list::<crate::EthPrimitives>(&tx), authorizations.as_slice());
+
+        for tx in [
+            crate::TransactionSigned::Legacy(TxLegacy::default().into_signed(signature)),
+            crate::TransactionSigned::Eip2930(TxEip2930::default().into_signed(signature)),
+            crate::TransactionSigned::Eip1559(TxEip1559::default().into_signed(signature)),
+            crate::TransactionSigned::Eip4844(TxEip4844::default().into_signed(signature)),
+        ] {
+            assert!(authorization_list::<crate::EthPrimitives>(&tx).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_blob_versioned_hashes() {
+        let signature = Signature::test_signature();
+        let hashes = vec![B256::with_last_byte(1), B256::with_last_byte(2)];
+        let tx = crate::TransactionSigned::Eip4844(
+            TxEip4844 { blob_versioned_hashes: hashes.clone(), ..Default::default() }
+                .into_signed(signature),
+        );
+        assert_eq!(blob_versioned_hashes::<crate::EthPrimitives>(&tx), hashes.as_slice());
+
+        let tx = crate::TransactionSigned::Legacy(TxLegacy::default().into_signed(signature));
+        assert!(blob_versioned_hashes::<crate::EthPrimitives>(&tx).is_empty());
+    }
+
+    #[test]
+    fn test_tx_fits_header() {
+        let header = alloy_consensus::Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(10),
+            ..Default::default()
+        };
+        let tx = |gas_limit, max_fee_per_gas| {
+            let tx = TxEip1559 {
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas: 1,
+                ..Default::default()
+            };
+            crate::TransactionSigned::Eip1559(tx.into_signed(Signature::test_signature()))
+        };
+
+        tx_fits_header::<crate::EthPrimitives>(&tx(21_000, 10), &header).unwrap();
+
+        assert_eq!(
+            tx_fits_header::<crate::EthPrimitives>(&tx(21_000, 9), &header),
+            Err(TxHeaderError::FeeCapBelowBaseFee { max_fee_per_gas: 9, base_fee: 10 })
+        );
+        assert_eq!(
+            tx_fits_header::<crate::EthPrimitives>(&tx(30_000_001, 10), &header),
+            Err(TxHeaderError::GasLimitExceedsBlock {
+                gas_limit: 30_000_001,
+                block_gas_limit: 30_000_000
+            })
+        );
+    }
 
     proptest! {
         #[test]
@@ -1,7 +1,11 @@
 //! Traits for execution.
 
 use crate::{ConfigureEvm, Database, OnStateHook};
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use alloy_consensus::{BlockHeader, Header};
 use alloy_eips::eip2718::WithEncoded;
 pub use alloy_evm::block::{BlockExecutor, BlockExecutorFactory};
@@ -9,10 +13,10 @@ use alloy_evm::{
     block::{CommitChanges, ExecutableTx},
     Evm, EvmEnv, EvmFactory,
 };
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256};
 use core::fmt::Debug;
 pub use reth_execution_errors::{
-    BlockExecutionError, BlockValidationError, InternalBlockExecutionError,
+    BlockExecutionError, BlockStateRootError, BlockValidationError, InternalBlockExecutionError,
 };
 use reth_execution_types::BlockExecutionResult;
 pub use reth_execution_types::{BlockExecutionOutput, ExecutionOutcome};
@@ -141,6 +145,48 @@ pub struct ExecuteOutput<R> {
     pub gas_used: u64,
 }
 
+/// All accounts and storage slots that were touched while executing a block.
+///
+/// An account is touched if it was loaded by any transaction or system call of the block, a slot is
+/// touched if it was read or written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TouchedAccounts {
+    /// Touched accounts and their touched storage slots.
+    pub accounts: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl TouchedAccounts {
+    /// Collects the touched accounts from the state after execution.
+    pub fn from_executed_state<DB>(state: &State<DB>) -> Self {
+        let mut touched = Self::default();
+        for (address, account) in &state.cache.accounts {
+            let slots = touched.accounts.entry(*address).or_default();
+            if let Some(account) = &account.account {
+                slots.extend(account.storage.keys().map(|slot| B256::from(*slot)));
+            }
+        }
+        // the cache doesn't retain the storage of destroyed accounts
+        for (address, account) in &state.bundle_state.state {
+            touched
+                .accounts
+                .entry(*address)
+                .or_default()
+                .extend(account.storage.keys().map(|slot| B256::from(*slot)));
+        }
+        touched
+    }
+
+    /// Returns `true` if the given account was touched.
+    pub fn contains_account(&self, address: &Address) -> bool {
+        self.accounts.contains_key(address)
+    }
+
+    /// Returns `true` if the given storage slot of the given account was touched.
+    pub fn contains_slot(&self, address: &Address, slot: &B256) -> bool {
+        self.accounts.get(address).is_some_and(|slots| slots.contains(slot))
+    }
+}
+
 /// Input for block building. Consumed by [`BlockAssembler`].
 ///
 /// This struct contains all the data needed by the [`BlockAssembler`] to create
@@ -441,7 +487,7 @@ where
         let hashed_state = state.hashed_post_state(&db.bundle_state);
         let (state_root, trie_updates) = state
             .state_root_with_updates(hashed_state.clone())
-            .map_err(BlockExecutionError::other)?;
+            .map_err(|err| BlockExecutionError::other(BlockStateRootError(err)))?;
 
         let (transactions, senders) =
             self.transactions.into_iter().map(|tx| tx.into_parts()).unzip();
@@ -545,10 +591,30 @@ where
     }
 }
 
+/// Executes the given block on top of `db` and returns all accounts and storage slots touched by
+/// its transactions and system calls.
+///
+/// Transactions are executed in order, so accounts that are only touched because of the effects of
+/// earlier transactions of the block are included as well.
+pub fn touched_accounts<F, DB>(
+    evm_config: &F,
+    db: DB,
+    block: &RecoveredBlock<<F::Primitives as NodePrimitives>::Block>,
+) -> Result<TouchedAccounts, BlockExecutionError>
+where
+    F: ConfigureEvm,
+    DB: Database,
+{
+    let mut touched = TouchedAccounts::default();
+    evm_config.batch_executor(db).execute_with_state_closure(block, |state| {
+        touched = TouchedAccounts::from_executed_state(state);
+    })?;
+    Ok(touched)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Address;
     use alloy_consensus::constants::KECCAK_EMPTY;
     use alloy_evm::block::state_changes::balance_increment_state;
     use alloy_primitives::{address, map::HashMap, U256};
@@ -6,6 +6,12 @@ use nybbles::Nibbles;
 use reth_storage_errors::{db::DatabaseError, provider::ProviderError};
 use thiserror::Error;
 
+/// Error returned if the state root of a block can't be computed after executing its
+/// transactions, e.g. when a block is built.
+#[derive(Error, Clone, Debug)]
+#[error("failed to compute state root: {0}")]
+pub struct BlockStateRootError(pub ProviderError);
+
 /// State root errors.
 #[derive(Error, PartialEq, Eq, Clone, Debug)]
 pub enum StateRootError {
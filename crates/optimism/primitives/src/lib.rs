@@ -36,11 +36,16 @@ pub struct OpPrimitives;
 impl reth_primitives_traits::NodePrimitives for OpPrimitives {
     type Block = OpBlock;
     type BlockHeader = alloy_consensus::Header;
+    type OmmerHeader = alloy_consensus::Header;
     type BlockBody = OpBlockBody;
     type SignedTx = OpTransactionSigned;
     type Receipt = OpReceipt;
 }
 
+impl reth_primitives_traits::PooledNodePrimitives for OpPrimitives {
+    type PooledTx = op_alloy_consensus::OpPooledTransaction;
+}
+
 /// Bincode-compatible serde implementations.
 #[cfg(feature = "serde-bincode-compat")]
 pub mod serde_bincode_compat {
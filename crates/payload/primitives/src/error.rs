@@ -3,8 +3,8 @@
 use alloc::{boxed::Box, string::ToString};
 use alloy_primitives::B256;
 use alloy_rpc_types_engine::{ForkchoiceUpdateError, PayloadError, PayloadStatusEnum};
-use core::error;
-use reth_errors::{BlockExecutionError, ProviderError, RethError};
+use core::{error, time::Duration};
+use reth_errors::{BlockExecutionError, BlockStateRootError, ProviderError, RethError};
 use tokio::sync::oneshot;
 
 /// Possible error variants during payload building.
@@ -28,6 +28,27 @@ pub enum PayloadBuilderError {
     /// Unrecoverable error during evm execution.
     #[error("evm execution error: {0}")]
     EvmExecutionError(Box<dyn core::error::Error + Send + Sync>),
+    /// The state root of the built block couldn't be computed, e.g. because of an inconsistent
+    /// trie.
+    #[error("state root error: {0}")]
+    StateRootError(ProviderError),
+    /// The gas used by the built block exceeds the gas limit it was built for.
+    #[error("block gas used {used} exceeds gas limit {limit}")]
+    GasLimitExceeded {
+        /// The gas used by the built block.
+        used: u64,
+        /// The gas limit of the block.
+        limit: u64,
+    },
+    /// The built payload doesn't conform to the engine API payload format.
+    #[error("invalid payload: {0}")]
+    InvalidPayload(#[from] PayloadError),
+    /// The deadline of the payload job was reached before the build attempt finished.
+    #[error("payload deadline exceeded after {elapsed:?}")]
+    DeadlineExceeded {
+        /// The time elapsed since the payload job was started.
+        elapsed: Duration,
+    },
     /// Any other payload building errors.
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -42,6 +63,17 @@ impl PayloadBuilderError {
         Self::EvmExecutionError(Box::new(error))
     }
 
+    /// Create a new error for a built block that used `used` gas, exceeding its gas `limit`.
+    pub const fn gas_limit_exceeded(used: u64, limit: u64) -> Self {
+        Self::GasLimitExceeded { used, limit }
+    }
+
+    /// Create a new error for a build attempt that was still running `elapsed` after the payload
+    /// job started, when the job's deadline was reached.
+    pub const fn deadline_exceeded(elapsed: Duration) -> Self {
+        Self::DeadlineExceeded { elapsed }
+    }
+
     /// Create a new error from a boxed error.
     pub fn other<E>(error: E) -> Self
     where
@@ -49,6 +81,34 @@ impl PayloadBuilderError {
     {
         Self::Other(Box::new(error))
     }
+
+    /// Returns `true` if the error is transient and building the payload may succeed if retried,
+    /// e.g. because the parent block wasn't available yet during a reorg.
+    pub const fn is_retryable(&self) -> bool {
+        match self {
+            Self::MissingParentHeader(_) | Self::MissingParentBlock(_) | Self::ChannelClosed => {
+                true
+            }
+            Self::MissingPayload |
+            Self::Internal(_) |
+            Self::EvmExecutionError(_) |
+            Self::StateRootError(_) |
+            Self::GasLimitExceeded { .. } |
+            Self::InvalidPayload(_) |
+            Self::DeadlineExceeded { .. } |
+            Self::Other(_) => false,
+        }
+    }
+
+    /// Returns `true` if only the failed build attempt is lost, and a payload built by an earlier
+    /// attempt of the job may still be resolved.
+    ///
+    /// This is the case for [`PayloadBuilderError::DeadlineExceeded`]: retrying isn't possible
+    /// within the current slot, but the best payload so far, or an empty payload, can be
+    /// delivered instead.
+    pub const fn is_best_effort_resolvable(&self) -> bool {
+        matches!(self, Self::DeadlineExceeded { .. })
+    }
 }
 
 impl From<ProviderError> for PayloadBuilderError {
@@ -65,7 +125,13 @@ impl From<oneshot::error::RecvError> for PayloadBuilderError {
 
 impl From<BlockExecutionError> for PayloadBuilderError {
     fn from(error: BlockExecutionError) -> Self {
-        Self::evm(error)
+        match error {
+            BlockExecutionError::Internal(error) => match error.downcast::<BlockStateRootError>() {
+                Ok(error) => Self::StateRootError(error.0),
+                Err(error) => Self::evm(BlockExecutionError::Internal(error)),
+            },
+            error => Self::evm(error),
+        }
     }
 }
 
@@ -120,6 +186,14 @@ pub enum VersionSpecificValidationError {
     /// root after Cancun
     #[error("no parent beacon block root post-cancun")]
     NoParentBeaconBlockRootPostCancun,
+    /// Thrown if the `ExecutionPayload` contains the `blobGasUsed` or `excessBlobGas` fields
+    /// before Cancun, or if they're passed to a pre-V3 method
+    #[error("blob gas fields not supported before Cancun")]
+    BlobGasFieldsNotSupportedBeforeCancun,
+    /// Thrown if the `ExecutionPayload` contains no `blobGasUsed` and `excessBlobGas` fields after
+    /// Cancun
+    #[error("no blob gas fields post-cancun")]
+    NoBlobGasFieldsPostCancun,
 }
 
 /// Error validating payload received over `newPayload` API.
@@ -128,6 +202,12 @@ pub enum NewPayloadError {
     /// Payload validation error.
     #[error(transparent)]
     Eth(#[from] PayloadError),
+    /// Thrown if the KZG proofs of the blob sidecars don't verify against the blobs and their
+    /// commitments.
+    ///
+    /// Unlike [`PayloadError::InvalidVersionedHashes`], the versioned hashes may match.
+    #[error("invalid blob proofs: {0}")]
+    InvalidBlobProofs(Box<dyn error::Error + Send + Sync>),
     /// Custom payload validation error.
     #[error(transparent)]
     Other(Box<dyn error::Error + Send + Sync>),
@@ -139,6 +219,12 @@ impl NewPayloadError {
     pub fn other(err: impl error::Error + Send + Sync + 'static) -> Self {
         Self::Other(Box::new(err))
     }
+
+    /// Creates instance of variant [`NewPayloadError::InvalidBlobProofs`].
+    #[inline]
+    pub fn invalid_blob_proofs(err: impl error::Error + Send + Sync + 'static) -> Self {
+        Self::InvalidBlobProofs(Box::new(err))
+    }
 }
 
 impl NewPayloadError {
@@ -153,6 +239,30 @@ impl NewPayloadError {
     pub const fn is_invalid_versioned_hashes(&self) -> bool {
         matches!(self, Self::Eth(PayloadError::InvalidVersionedHashes))
     }
+
+    /// Returns `true` if the error is caused by blob proofs that failed KZG verification (Cancun).
+    #[inline]
+    pub const fn is_invalid_blob_proofs(&self) -> bool {
+        matches!(self, Self::InvalidBlobProofs(_))
+    }
+
+    /// Returns `true` if the payload itself is malformed, e.g. it can't be decoded into a block or
+    /// its fields are invalid for the fork of its timestamp.
+    ///
+    /// Such a payload is invalid regardless of the node's state, unlike [`NewPayloadError::Other`]
+    /// which may be caused by a transient failure. Block hash and versioned hash mismatches are
+    /// not considered structural, see [`Self::is_block_hash_mismatch`] and
+    /// [`Self::is_invalid_versioned_hashes`].
+    #[inline]
+    pub const fn is_structural(&self) -> bool {
+        matches!(
+            self,
+            Self::Eth(error) if !matches!(
+                error,
+                PayloadError::BlockHash { .. } | PayloadError::InvalidVersionedHashes
+            )
+        )
+    }
 }
 
 impl From<NewPayloadError> for PayloadStatusEnum {
@@ -177,13 +287,152 @@ pub enum InvalidPayloadAttributesError {
     /// Thrown if the timestamp of the payload attributes is invalid according to the engine specs.
     #[error("invalid timestamp")]
     InvalidTimestamp,
+    /// Thrown if the indices of the withdrawals of the payload attributes aren't strictly
+    /// increasing.
+    #[error(
+        "withdrawal index {index} is not greater than the previous withdrawal index {previous}"
+    )]
+    NonMonotonicWithdrawals {
+        /// The index of the preceding withdrawal.
+        previous: u64,
+        /// The index of the out-of-order withdrawal.
+        index: u64,
+    },
     /// Another type of error that is not covered by the above variants.
     #[error("Invalid params: {0}")]
     InvalidParams(#[from] Box<dyn core::error::Error + Send + Sync>),
 }
 
 impl From<InvalidPayloadAttributesError> for ForkchoiceUpdateError {
-    fn from(_: InvalidPayloadAttributesError) -> Self {
-        Self::UpdatedInvalidPayloadAttributes
+    fn from(error: InvalidPayloadAttributesError) -> Self {
+        match error {
+            InvalidPayloadAttributesError::InvalidTimestamp |
+            InvalidPayloadAttributesError::NonMonotonicWithdrawals { .. } |
+            InvalidPayloadAttributesError::InvalidParams(_) => {
+                Self::UpdatedInvalidPayloadAttributes
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_root_error_from_block_execution_error() {
+        let error = BlockExecutionError::other(BlockStateRootError(
+            ProviderError::BlockHashNotFound(B256::ZERO),
+        ));
+        assert!(matches!(
+            PayloadBuilderError::from(error),
+            PayloadBuilderError::StateRootError(ProviderError::BlockHashNotFound(_))
+        ));
+
+        let error = BlockExecutionError::msg("execution failed");
+        assert!(matches!(
+            PayloadBuilderError::from(error),
+            PayloadBuilderError::EvmExecutionError(_)
+        ));
+    }
+
+    #[test]
+    fn gas_limit_exceeded_display() {
+        let error = PayloadBuilderError::gas_limit_exceeded(30_000_001, 30_000_000);
+        assert!(matches!(
+            error,
+            PayloadBuilderError::GasLimitExceeded { used: 30_000_001, limit: 30_000_000 }
+        ));
+        assert_eq!(error.to_string(), "block gas used 30000001 exceeds gas limit 30000000");
+    }
+
+    #[test]
+    fn non_monotonic_withdrawals() {
+        let error =
+            InvalidPayloadAttributesError::NonMonotonicWithdrawals { previous: 5, index: 5 };
+        assert_eq!(
+            error.to_string(),
+            "withdrawal index 5 is not greater than the previous withdrawal index 5"
+        );
+        assert!(matches!(
+            ForkchoiceUpdateError::from(error),
+            ForkchoiceUpdateError::UpdatedInvalidPayloadAttributes
+        ));
+    }
+
+    #[test]
+    fn structural_new_payload_errors() {
+        assert!(NewPayloadError::Eth(PayloadError::ExtraData(Default::default())).is_structural());
+        assert!(NewPayloadError::Eth(PayloadError::BaseFee(Default::default())).is_structural());
+        assert!(!NewPayloadError::Eth(PayloadError::BlockHash {
+            execution: B256::ZERO,
+            consensus: B256::with_last_byte(1),
+        })
+        .is_structural());
+        assert!(!NewPayloadError::Eth(PayloadError::InvalidVersionedHashes).is_structural());
+        assert!(
+            !NewPayloadError::other(ProviderError::BlockHashNotFound(B256::ZERO)).is_structural()
+        );
+    }
+
+    #[test]
+    fn from_payload_error() {
+        let error = PayloadBuilderError::from(PayloadError::InvalidVersionedHashes);
+        assert!(matches!(
+            error,
+            PayloadBuilderError::InvalidPayload(PayloadError::InvalidVersionedHashes)
+        ));
+        assert_eq!(
+            error.to_string(),
+            "invalid payload: expected blob versioned hashes do not match the given transactions"
+        );
+    }
+
+    #[test]
+    fn retryable_errors() {
+        let errors = [
+            (PayloadBuilderError::MissingParentHeader(B256::ZERO), true),
+            (PayloadBuilderError::MissingParentBlock(B256::ZERO), true),
+            (PayloadBuilderError::ChannelClosed, true),
+            (PayloadBuilderError::MissingPayload, false),
+            (PayloadBuilderError::Internal(RethError::msg("internal")), false),
+            (PayloadBuilderError::evm(BlockExecutionError::msg("execution failed")), false),
+            (
+                PayloadBuilderError::StateRootError(ProviderError::BlockHashNotFound(B256::ZERO)),
+                false,
+            ),
+            (PayloadBuilderError::gas_limit_exceeded(2, 1), false),
+            (PayloadBuilderError::InvalidPayload(PayloadError::InvalidVersionedHashes), false),
+            (PayloadBuilderError::deadline_exceeded(Duration::from_secs(12)), false),
+            (PayloadBuilderError::other(BlockExecutionError::msg("other")), false),
+        ];
+        for (error, retryable) in errors {
+            assert_eq!(error.is_retryable(), retryable, "{error:?}");
+        }
+    }
+
+    #[test]
+    fn deadline_exceeded() {
+        let error = PayloadBuilderError::deadline_exceeded(Duration::from_millis(1500));
+        assert!(error.is_best_effort_resolvable());
+        assert_eq!(error.to_string(), "payload deadline exceeded after 1.5s");
+        assert!(!PayloadBuilderError::MissingPayload.is_best_effort_resolvable());
+    }
+
+    #[test]
+    fn invalid_blob_proofs() {
+        let error = NewPayloadError::invalid_blob_proofs(RethError::msg("proof mismatch"));
+        assert!(error.is_invalid_blob_proofs());
+        assert!(!error.is_invalid_versioned_hashes());
+        assert_eq!(
+            PayloadStatusEnum::from(error),
+            PayloadStatusEnum::Invalid {
+                validation_error: "invalid blob proofs: proof mismatch".to_string()
+            }
+        );
+
+        let error = NewPayloadError::Eth(PayloadError::InvalidVersionedHashes);
+        assert!(error.is_invalid_versioned_hashes());
+        assert!(!error.is_invalid_blob_proofs());
     }
 }
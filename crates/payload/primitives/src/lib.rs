@@ -14,6 +14,7 @@
 extern crate alloc;
 
 use crate::alloc::string::ToString;
+use alloy_eips::eip4895::Withdrawal;
 use alloy_primitives::Bytes;
 use reth_chainspec::EthereumHardforks;
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
@@ -305,6 +306,44 @@ pub fn validate_parent_beacon_block_root_presence<T: EthereumHardforks>(
     Ok(())
 }
 
+/// Validates the presence of the `blobGasUsed` and `excessBlobGas` fields of an execution payload
+/// according to the payload timestamp and the message version.
+///
+/// After Cancun, the blob gas fields must be present. Before Cancun, or for pre-V3 messages, they
+/// must be absent.
+pub fn validate_blob_gas_fields_presence<T: EthereumHardforks>(
+    chain_spec: &T,
+    version: EngineApiMessageVersion,
+    validation_kind: MessageValidationKind,
+    timestamp: u64,
+    has_blob_gas_fields: bool,
+) -> Result<(), EngineObjectValidationError> {
+    let is_cancun_active = chain_spec.is_cancun_active_at_timestamp(timestamp);
+
+    match version {
+        EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
+            if has_blob_gas_fields {
+                return Err(validation_kind.to_error(
+                    VersionSpecificValidationError::BlobGasFieldsNotSupportedBeforeCancun,
+                ))
+            }
+        }
+        EngineApiMessageVersion::V3 | EngineApiMessageVersion::V4 | EngineApiMessageVersion::V5 => {
+            if is_cancun_active && !has_blob_gas_fields {
+                return Err(validation_kind
+                    .to_error(VersionSpecificValidationError::NoBlobGasFieldsPostCancun))
+            }
+            if !is_cancun_active && has_blob_gas_fields {
+                return Err(validation_kind.to_error(
+                    VersionSpecificValidationError::BlobGasFieldsNotSupportedBeforeCancun,
+                ))
+            }
+        }
+    };
+
+    Ok(())
+}
+
 /// A type that represents whether or not we are validating a payload or payload attributes.
 ///
 /// This is used to ensure that the correct error code is returned when validating the payload or
@@ -361,7 +400,18 @@ where
         payload_or_attrs.message_validation_kind(),
         payload_or_attrs.timestamp(),
         payload_or_attrs.parent_beacon_block_root().is_some(),
-    )
+    )?;
+    // payload attributes don't carry blob gas fields
+    if let PayloadOrAttributes::ExecutionPayload(payload) = payload_or_attrs {
+        validate_blob_gas_fields_presence(
+            chain_spec,
+            version,
+            MessageValidationKind::Payload,
+            payload.timestamp(),
+            payload.blob_gas_used().is_some() || payload.excess_blob_gas().is_some(),
+        )?;
+    }
+    Ok(())
 }
 
 /// The version of Engine API message.
@@ -484,6 +534,23 @@ pub fn validate_execution_requests(requests: &[Bytes]) -> Result<(), EngineObjec
     Ok(())
 }
 
+/// Validates that the indices of the given withdrawals of payload attributes are strictly
+/// increasing.
+///
+/// Returns [`InvalidPayloadAttributesError::NonMonotonicWithdrawals`] for the first withdrawal
+/// whose index isn't greater than the index of the preceding withdrawal.
+pub fn validate_withdrawal_indices(
+    withdrawals: &[Withdrawal],
+) -> Result<(), InvalidPayloadAttributesError> {
+    for pair in withdrawals.windows(2) {
+        let (previous, index) = (pair[0].index, pair[1].index);
+        if index <= previous {
+            return Err(InvalidPayloadAttributesError::NonMonotonicWithdrawals { previous, index })
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,6 +561,60 @@ mod tests {
         assert!(EngineApiMessageVersion::V4 > EngineApiMessageVersion::V3);
     }
 
+    #[test]
+    fn withdrawal_indices_validation() {
+        let withdrawal = |index| Withdrawal { index, ..Default::default() };
+        assert_matches!(validate_withdrawal_indices(&[]), Ok(()));
+        assert_matches!(
+            validate_withdrawal_indices(&[withdrawal(1), withdrawal(2), withdrawal(4)]),
+            Ok(())
+        );
+        assert_matches!(
+            validate_withdrawal_indices(&[withdrawal(1), withdrawal(3), withdrawal(2)]),
+            Err(InvalidPayloadAttributesError::NonMonotonicWithdrawals { previous: 3, index: 2 })
+        );
+        assert_matches!(
+            validate_withdrawal_indices(&[withdrawal(1), withdrawal(1)]),
+            Err(InvalidPayloadAttributesError::NonMonotonicWithdrawals { previous: 1, index: 1 })
+        );
+    }
+
+    #[test]
+    fn blob_gas_fields_validation() {
+        let chain_spec: &reth_chainspec::ChainSpec = &reth_chainspec::MAINNET;
+        let cancun = 1_710_338_135;
+        let validate = |version, timestamp, has_blob_gas_fields| {
+            validate_blob_gas_fields_presence(
+                chain_spec,
+                version,
+                MessageValidationKind::Payload,
+                timestamp,
+                has_blob_gas_fields,
+            )
+        };
+
+        assert_matches!(validate(EngineApiMessageVersion::V3, cancun, true), Ok(()));
+        assert_matches!(validate(EngineApiMessageVersion::V2, cancun - 1, false), Ok(()));
+        assert_matches!(
+            validate(EngineApiMessageVersion::V3, cancun, false),
+            Err(EngineObjectValidationError::Payload(
+                VersionSpecificValidationError::NoBlobGasFieldsPostCancun
+            ))
+        );
+        assert_matches!(
+            validate(EngineApiMessageVersion::V3, cancun - 1, true),
+            Err(EngineObjectValidationError::Payload(
+                VersionSpecificValidationError::BlobGasFieldsNotSupportedBeforeCancun
+            ))
+        );
+        assert_matches!(
+            validate(EngineApiMessageVersion::V2, cancun, true),
+            Err(EngineObjectValidationError::Payload(
+                VersionSpecificValidationError::BlobGasFieldsNotSupportedBeforeCancun
+            ))
+        );
+    }
+
     #[test]
     fn execution_requests_validation() {
         assert_matches!(validate_execution_requests(&[]), Ok(()));
@@ -40,6 +40,22 @@ pub trait ExecutionPayload:
 
     /// Returns the total gas consumed by all transactions in this block.
     fn gas_used(&self) -> u64;
+
+    /// Returns the total blob gas consumed by the transactions in this block.
+    ///
+    /// Returns `None` for pre-Cancun payloads. By default this is `None`, payload types of chains
+    /// with blob transactions must override it.
+    fn blob_gas_used(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the excess blob gas of this block.
+    ///
+    /// Returns `None` for pre-Cancun payloads. By default this is `None`, payload types of chains
+    /// with blob transactions must override it.
+    fn excess_blob_gas(&self) -> Option<u64> {
+        None
+    }
 }
 
 impl ExecutionPayload for ExecutionData {
@@ -70,6 +86,14 @@ impl ExecutionPayload for ExecutionData {
     fn gas_used(&self) -> u64 {
         self.payload.as_v1().gas_used
     }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.payload.as_v3().map(|payload| payload.blob_gas_used)
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.payload.as_v3().map(|payload| payload.excess_blob_gas)
+    }
 }
 
 /// A unified type for handling both execution payloads and payload attributes.
@@ -173,6 +197,14 @@ impl ExecutionPayload for op_alloy_rpc_types_engine::OpExecutionData {
     fn gas_used(&self) -> u64 {
         self.payload.as_v1().gas_used
     }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.payload.as_v3().map(|payload| payload.blob_gas_used)
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.payload.as_v3().map(|payload| payload.excess_blob_gas)
+    }
 }
 
 /// Extended functionality for Ethereum execution payloads
@@ -109,7 +109,8 @@ pub trait BlockBody:
 
     /// Calculate the withdrawals root for the block body.
     ///
-    /// Returns `RecoveryError` if there are no withdrawals in the block.
+    /// Returns `None` if the block has no withdrawals, i.e. before Shanghai. A post-Shanghai block
+    /// without withdrawals has the root of an empty trie.
     fn calculate_withdrawals_root(&self) -> Option<B256> {
         self.withdrawals().map(|withdrawals| {
             alloy_consensus::proofs::calculate_withdrawals_root(withdrawals.as_slice())
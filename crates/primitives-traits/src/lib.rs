@@ -126,7 +126,7 @@ pub mod account;
 pub use account::{Account, Bytecode};
 
 pub mod receipt;
-pub use receipt::{FullReceipt, Receipt};
+pub use receipt::{CumulativeGasError, FullReceipt, Receipt};
 
 pub mod transaction;
 pub use alloy_consensus::{
@@ -187,7 +187,14 @@ pub use size::InMemorySize;
 
 /// Node traits
 pub mod node;
-pub use node::{BlockTy, BodyTy, FullNodePrimitives, HeaderTy, NodePrimitives, ReceiptTy, TxTy};
+pub use node::{
+    assert_coherent, authorization_list, blob_versioned_hashes, calldata_bytes, header_slot,
+    header_timestamp_for_slot, ommers, receipts_gas_used, senders_and_kinds, tx_fits_header,
+    BlockTy, BodyTy, FullNodePrimitives, FullNodePrimitivesSerde, HeaderTy, NodePrimitives,
+    OmmerTy, PooledNodePrimitives, PooledTxTy, ReceiptTy, SlotConfig, TxTy,
+};
+#[cfg(feature = "reth-codec")]
+pub use node::{compact_decode_header, compact_encode_header};
 
 /// Helper trait that requires de-/serialize implementation since `serde` feature is enabled.
 #[cfg(feature = "serde")]
@@ -1,7 +1,13 @@
 use crate::{
-    Block, FullBlock, FullBlockBody, FullBlockHeader, FullReceipt, FullSignedTx,
-    MaybeSerdeBincodeCompat, Receipt,
+    block::RecoveredBlock, receipt::CumulativeGasError, transaction::error::TxHeaderError, Block,
+    BlockBody, FullBlock, FullBlockBody, FullBlockHeader, FullReceipt, FullSignedTx,
+    MaybeSerdeBincodeCompat, Receipt, SignedTransaction,
 };
+use alloc::vec::Vec;
+use alloy_consensus::{BlockHeader as _, Transaction as _, TxReceipt as _};
+use alloy_eips::eip7702::SignedAuthorization;
+use alloy_hardforks::EthereumHardforks;
+use alloy_primitives::{Address, Bloom, TxKind, B256};
 use core::fmt;
 
 /// Configures all the primitive types of the node.
@@ -16,8 +22,13 @@ pub trait NodePrimitives:
     type Block: Block<Header = Self::BlockHeader, Body = Self::BlockBody> + MaybeSerdeBincodeCompat;
     /// Block header primitive.
     type BlockHeader: FullBlockHeader;
+    /// Ommer header primitive.
+    ///
+    /// Since [`Block`] requires the ommers of its body to be of its header type, this is the same
+    /// as [`NodePrimitives::BlockHeader`].
+    type OmmerHeader: FullBlockHeader;
     /// Block body primitive.
-    type BlockBody: FullBlockBody<Transaction = Self::SignedTx, OmmerHeader = Self::BlockHeader>;
+    type BlockBody: FullBlockBody<Transaction = Self::SignedTx, OmmerHeader = Self::OmmerHeader>;
     /// Signed version of the transaction type.
     ///
     /// This represents the transaction as it exists in the blockchain - the consensus
@@ -25,6 +36,37 @@ pub trait NodePrimitives:
     type SignedTx: FullSignedTx;
     /// A receipt.
     type Receipt: Receipt;
+
+    /// The receipts root of a block without any receipts.
+    const EMPTY_RECEIPTS_ROOT: B256 = alloy_consensus::constants::EMPTY_RECEIPTS;
+
+    /// The logs bloom of a block without any logs.
+    const EMPTY_LOGS_BLOOM: Bloom = <Self::Receipt as Receipt>::EMPTY_LOGS_BLOOM;
+
+    /// The approximate number of logs of a receipt, used as capacity hint when decoding the logs
+    /// of receipts.
+    ///
+    /// This is only a hint for preallocation and never limits the number of logs.
+    const APPROX_LOGS_PER_RECEIPT: usize = 4;
+
+    /// Returns an empty block header, e.g. as a placeholder in generic code.
+    ///
+    /// By default this is the [`Default`] header.
+    fn empty_header() -> Self::BlockHeader {
+        Self::BlockHeader::default()
+    }
+
+    /// Returns the recipient of the block reward of the given header on the given chain.
+    ///
+    /// By default this is the header's beneficiary. Chains that credit block rewards to a
+    /// different account, e.g. a fee recipient contract activated by a hardfork, can override
+    /// this.
+    fn reward_recipient<C>(header: &Self::BlockHeader, _chain_spec: C) -> Address
+    where
+        C: EthereumHardforks,
+    {
+        header.beneficiary()
+    }
 }
 /// Helper trait that sets trait bounds on [`NodePrimitives`].
 pub trait FullNodePrimitives
@@ -66,6 +108,60 @@ impl<T> FullNodePrimitives for T where
 {
 }
 
+/// Helper trait for [`NodePrimitives`] whose block, block header, block body, signed transaction
+/// and receipt types all implement [`MaybeSerdeBincodeCompat`].
+///
+/// This allows code that round-trips all primitive types, e.g. storage codecs, to be generic over
+/// the primitives without spelling out the bound on each type.
+pub trait FullNodePrimitivesSerde
+where
+    Self: NodePrimitives<
+        BlockHeader: MaybeSerdeBincodeCompat,
+        BlockBody: MaybeSerdeBincodeCompat,
+        SignedTx: MaybeSerdeBincodeCompat,
+        Receipt: MaybeSerdeBincodeCompat,
+    >,
+{
+}
+
+impl<T> FullNodePrimitivesSerde for T where
+    T: NodePrimitives<
+        BlockHeader: MaybeSerdeBincodeCompat,
+        BlockBody: MaybeSerdeBincodeCompat,
+        SignedTx: MaybeSerdeBincodeCompat,
+        Receipt: MaybeSerdeBincodeCompat,
+    >
+{
+}
+
+/// Extension of [`NodePrimitives`] for nodes whose transactions have a pooled form.
+///
+/// This is separate from [`NodePrimitives`] so that primitives without a pooled transaction type,
+/// e.g. of nodes without a transaction pool, don't need to provide one.
+pub trait PooledNodePrimitives: NodePrimitives {
+    /// Pooled version of the transaction type.
+    ///
+    /// This represents the transaction as it is propagated over the network and kept in the
+    /// transaction pool, e.g. EIP-4844 transactions including their blob sidecar. Not every
+    /// [`NodePrimitives::SignedTx`] has a pooled form, e.g. deposit transactions.
+    type PooledTx: SignedTransaction + TryFrom<Self::SignedTx> + Into<Self::SignedTx>;
+}
+
+/// Asserts that the block, block header, block body and signed transaction types of the given
+/// [`NodePrimitives`] are wired consistently.
+///
+/// The bounds of [`NodePrimitives`] already require this, but a mismatch surfaces as an error about
+/// the associated type bounds of the trait. Evaluating `const _: () = assert_coherent::<N>();`
+/// next to a custom implementation, or calling this in a test, names the mismatching types
+/// instead.
+pub const fn assert_coherent<N>()
+where
+    N: NodePrimitives,
+    N::Block: Block<Header = N::BlockHeader, Body = N::BlockBody>,
+    N::BlockBody: BlockBody<Transaction = N::SignedTx, OmmerHeader = N::OmmerHeader>,
+{
+}
+
 /// Helper adapter type for accessing [`NodePrimitives`] block header types.
 pub type HeaderTy<N> = <N as NodePrimitives>::BlockHeader;
 
@@ -75,8 +171,185 @@ pub type BodyTy<N> = <N as NodePrimitives>::BlockBody;
 /// Helper adapter type for accessing [`NodePrimitives`] block types.
 pub type BlockTy<N> = <N as NodePrimitives>::Block;
 
+/// Helper adapter type for accessing [`NodePrimitives`] ommer header types.
+pub type OmmerTy<N> = <N as NodePrimitives>::OmmerHeader;
+
 /// Helper adapter type for accessing [`NodePrimitives`] receipt types.
 pub type ReceiptTy<N> = <N as NodePrimitives>::Receipt;
 
 /// Helper adapter type for accessing [`NodePrimitives`] signed transaction types.
 pub type TxTy<N> = <N as NodePrimitives>::SignedTx;
+
+/// Helper adapter type for accessing [`PooledNodePrimitives`] pooled transaction types.
+pub type PooledTxTy<N> = <N as PooledNodePrimitives>::PooledTx;
+
+/// Checks that the given [`NodePrimitives`] transaction fits into a block with the given header.
+///
+/// The gas limit of the transaction must not exceed the block gas limit, and if the block has a
+/// base fee, the max fee per gas of the transaction must cover it. The max priority fee per gas
+/// must not exceed the max fee per gas.
+pub fn tx_fits_header<N: NodePrimitives>(
+    tx: &TxTy<N>,
+    header: &HeaderTy<N>,
+) -> Result<(), TxHeaderError> {
+    if tx.gas_limit() > header.gas_limit() {
+        return Err(TxHeaderError::GasLimitExceedsBlock {
+            gas_limit: tx.gas_limit(),
+            block_gas_limit: header.gas_limit(),
+        })
+    }
+
+    let max_fee_per_gas = tx.max_fee_per_gas();
+    if let Some(max_priority_fee_per_gas) = tx.max_priority_fee_per_gas() {
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            return Err(TxHeaderError::TipAboveFeeCap { max_priority_fee_per_gas, max_fee_per_gas })
+        }
+    }
+    if let Some(base_fee) = header.base_fee_per_gas() {
+        if max_fee_per_gas < base_fee as u128 {
+            return Err(TxHeaderError::FeeCapBelowBaseFee { max_fee_per_gas, base_fee })
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the gas used by each transaction of a block, given the block's [`NodePrimitives`]
+/// receipts.
+///
+/// Returns an error if the cumulative gas used decreases, i.e. the receipts are corrupt or out of
+/// order.
+pub fn receipts_gas_used<N: NodePrimitives>(
+    receipts: &[ReceiptTy<N>],
+) -> Result<Vec<u64>, CumulativeGasError> {
+    let mut previous = 0;
+    receipts
+        .iter()
+        .enumerate()
+        .map(|(index, receipt)| {
+            let cumulative_gas_used = receipt.cumulative_gas_used();
+            let gas_used = cumulative_gas_used.checked_sub(previous).ok_or(CumulativeGasError {
+                index,
+                cumulative_gas_used,
+                previous,
+            })?;
+            previous = cumulative_gas_used;
+            Ok(gas_used)
+        })
+        .collect()
+}
+
+/// Returns the number of zero and non-zero bytes of the input of the given [`NodePrimitives`]
+/// transaction, as `(zero, nonzero)`.
+///
+/// These are the byte counts that intrinsic gas and the EIP-7623 calldata floor cost are priced
+/// by.
+pub fn calldata_bytes<N: NodePrimitives>(tx: &TxTy<N>) -> (usize, usize) {
+    let input = tx.input();
+    let nonzero = input.iter().filter(|byte| **byte != 0).count();
+    (input.len() - nonzero, nonzero)
+}
+
+/// Returns the EIP-7702 authorization list of the given [`NodePrimitives`] transaction.
+///
+/// Returns an empty list for all transaction types other than EIP-7702.
+pub fn authorization_list<N: NodePrimitives>(tx: &TxTy<N>) -> &[SignedAuthorization] {
+    tx.authorization_list().unwrap_or_default()
+}
+
+/// Returns the EIP-4844 blob versioned hashes of the given [`NodePrimitives`] transaction.
+///
+/// Returns an empty list for all transaction types other than EIP-4844.
+pub fn blob_versioned_hashes<N: NodePrimitives>(tx: &TxTy<N>) -> &[B256] {
+    tx.blob_versioned_hashes().unwrap_or_default()
+}
+
+/// Returns the ommer headers of the given [`NodePrimitives`] block.
+///
+/// Returns an empty list if the block body doesn't have ommers, e.g. after the merge.
+pub fn ommers<N: NodePrimitives>(block: &BlockTy<N>) -> &[OmmerTy<N>] {
+    block.body().ommers().unwrap_or_default()
+}
+
+/// Returns the sender and [`TxKind`] of each transaction of the given recovered [`NodePrimitives`]
+/// block, in block order.
+///
+/// The senders are the ones cached by the [`RecoveredBlock`], so no signature recovery is done.
+pub fn senders_and_kinds<N: NodePrimitives>(
+    block: &RecoveredBlock<BlockTy<N>>,
+) -> impl Iterator<Item = (Address, TxKind)> + '_ {
+    block.transactions_with_sender().map(|(sender, tx)| (*sender, tx.kind()))
+}
+
+/// Beacon chain parameters that determine the slot of a timestamp.
+///
+/// These depend on the chain, see `EthChainSpec::slot_config` for the parameters of a chain spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotConfig {
+    /// The timestamp of the beacon chain genesis, i.e. the start of slot 0.
+    pub genesis_time: u64,
+    /// The duration of a slot in seconds.
+    pub seconds_per_slot: u64,
+}
+
+impl SlotConfig {
+    /// Creates the slot parameters of a beacon chain with the given genesis time and slot
+    /// duration.
+    pub const fn new(genesis_time: u64, seconds_per_slot: u64) -> Self {
+        Self { genesis_time, seconds_per_slot }
+    }
+
+    /// Returns the slot the given timestamp falls into, or `None` if it precedes the genesis.
+    pub const fn slot_at(&self, timestamp: u64) -> Option<u64> {
+        if timestamp < self.genesis_time || self.seconds_per_slot == 0 {
+            return None
+        }
+        Some((timestamp - self.genesis_time) / self.seconds_per_slot)
+    }
+
+    /// Returns the timestamp of the start of the given slot, or `None` if it overflows.
+    pub const fn timestamp_at(&self, slot: u64) -> Option<u64> {
+        match slot.checked_mul(self.seconds_per_slot) {
+            Some(offset) => self.genesis_time.checked_add(offset),
+            None => None,
+        }
+    }
+}
+
+/// Returns the beacon slot of the given [`NodePrimitives`] block header, or `None` if the block
+/// precedes the beacon chain genesis.
+///
+/// See also [`header_timestamp_for_slot`].
+pub fn header_slot<N: NodePrimitives>(header: &HeaderTy<N>, config: &SlotConfig) -> Option<u64> {
+    config.slot_at(header.timestamp())
+}
+
+/// Returns the timestamp of a [`NodePrimitives`] block header proposed in the given beacon slot,
+/// or `None` if it overflows.
+pub const fn header_timestamp_for_slot<N: NodePrimitives>(
+    slot: u64,
+    config: &SlotConfig,
+) -> Option<u64> {
+    config.timestamp_at(slot)
+}
+
+/// Encodes the given [`NodePrimitives`] block header using its compact storage encoding.
+///
+/// See also [`compact_decode_header`].
+#[cfg(feature = "reth-codec")]
+pub fn compact_encode_header<N: NodePrimitives>(header: &HeaderTy<N>) -> alloc::vec::Vec<u8> {
+    let mut buf = alloc::vec::Vec::new();
+    reth_codecs::Compact::to_compact(header, &mut buf);
+    buf
+}
+
+/// Decodes a [`NodePrimitives`] block header that was encoded with [`compact_encode_header`].
+///
+/// # Panics
+///
+/// Panics if `buf` isn't a valid compact encoding of a header, e.g. if it's truncated. This is
+/// only meant for trusted input such as the header tables of the database.
+#[cfg(feature = "reth-codec")]
+pub fn compact_decode_header<N: NodePrimitives>(buf: &[u8]) -> HeaderTy<N> {
+    reth_codecs::Compact::from_compact(buf, buf.len()).0
+}
@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 use alloy_consensus::{
     Eip2718EncodableReceipt, RlpDecodableReceipt, RlpEncodableReceipt, TxReceipt, Typed2718,
 };
+use alloy_primitives::Bloom;
 use alloy_rlp::{Decodable, Encodable};
 use core::fmt;
 
@@ -31,6 +32,15 @@ pub trait Receipt:
     + InMemorySize
     + MaybeSerdeBincodeCompat
 {
+    /// The logs bloom of a receipt that did not emit any logs.
+    const EMPTY_LOGS_BLOOM: Bloom = Bloom::ZERO;
+
+    /// Returns `true` if the receipt did not emit any logs.
+    ///
+    /// The logs bloom of such a receipt is [`Self::EMPTY_LOGS_BLOOM`].
+    fn has_no_logs(&self) -> bool {
+        self.logs().is_empty()
+    }
 }
 
 // Blanket implementation for any type that satisfies all the supertrait bounds
@@ -65,3 +75,17 @@ where
         .map(|(id, receipt)| (id as u64, receipt.cumulative_gas_used()))
         .collect()
 }
+
+/// Error returned when the cumulative gas used of a sequence of receipts decreases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "cumulative gas used of receipt {index} is {cumulative_gas_used}, below the previous {previous}"
+)]
+pub struct CumulativeGasError {
+    /// The index of the receipt whose cumulative gas used is below the previous one.
+    pub index: usize,
+    /// The cumulative gas used of the receipt.
+    pub cumulative_gas_used: u64,
+    /// The cumulative gas used of the previous receipt.
+    pub previous: u64,
+}
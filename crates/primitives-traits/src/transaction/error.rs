@@ -1,6 +1,6 @@
 //! Various error variants that can happen when working with transactions.
 
-use crate::GotExpectedBoxed;
+use crate::{transaction::signed::RecoveryError, GotExpectedBoxed};
 use alloy_primitives::U256;
 
 /// Represents error variants that can happen when trying to validate a transaction.
@@ -66,6 +66,36 @@ pub enum InvalidTransactionError {
     GasLimitTooHigh,
 }
 
+/// Represents error variants that can happen when checking a transaction against the header of the
+/// block it is included in.
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum TxHeaderError {
+    /// The gas limit of the transaction exceeds the gas limit of the block.
+    #[error("transaction gas limit {gas_limit} exceeds block gas limit {block_gas_limit}")]
+    GasLimitExceedsBlock {
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The gas limit of the block.
+        block_gas_limit: u64,
+    },
+    /// The max fee per gas of the transaction doesn't cover the base fee of the block.
+    #[error("max fee per gas {max_fee_per_gas} less than block base fee {base_fee}")]
+    FeeCapBelowBaseFee {
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+        /// The base fee of the block.
+        base_fee: u64,
+    },
+    /// The max priority fee per gas of the transaction is higher than its max fee per gas.
+    #[error("max priority fee per gas {max_priority_fee_per_gas} higher than max fee per gas {max_fee_per_gas}")]
+    TipAboveFeeCap {
+        /// The max priority fee per gas of the transaction.
+        max_priority_fee_per_gas: u128,
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+    },
+}
+
 /// Represents error variants that can happen when trying to convert a transaction to pooled
 /// transaction.
 #[derive(Debug, Clone, Eq, PartialEq, derive_more::Display, derive_more::Error)]
@@ -86,3 +116,14 @@ pub enum TryFromRecoveredTransactionError {
     #[error("Blob sidecar missing for an EIP-4844 transaction")]
     BlobSidecarMissing,
 }
+
+/// Error returned when the sender of a transaction in a batch of transactions can't be recovered.
+#[derive(Debug, thiserror::Error)]
+#[error("failed to recover sender of transaction at index {index}")]
+pub struct SenderRecoveryError {
+    /// The index of the transaction with the invalid signature.
+    pub index: usize,
+    /// The underlying recovery error.
+    #[source]
+    pub error: RecoveryError,
+}
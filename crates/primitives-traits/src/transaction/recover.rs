@@ -8,10 +8,15 @@ pub use iter::*;
 
 #[cfg(feature = "rayon")]
 mod rayon {
-    use crate::{transaction::signed::RecoveryError, SignedTransaction};
+    use crate::{
+        transaction::{error::SenderRecoveryError, signed::RecoveryError},
+        NodePrimitives, SignedTransaction, SignerRecoverable, TxTy,
+    };
     use alloc::vec::Vec;
     use alloy_primitives::Address;
-    use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+    use rayon::prelude::{
+        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+    };
 
     /// Recovers a list of signers from a transaction list iterator.
     ///
@@ -35,6 +40,21 @@ mod rayon {
     {
         txes.into_par_iter().map(|tx| tx.recover_signer_unchecked()).collect()
     }
+
+    /// Recovers the senders of the given [`NodePrimitives`] transactions in parallel.
+    ///
+    /// The senders are returned in the same order as the given transactions. If a signature is
+    /// invalid, the returned [`SenderRecoveryError`] contains the index of the transaction.
+    pub fn recover_senders_par<N: NodePrimitives>(
+        txs: &[TxTy<N>],
+    ) -> Result<Vec<Address>, SenderRecoveryError> {
+        txs.par_iter()
+            .enumerate()
+            .map(|(index, tx)| {
+                tx.recover_signer().map_err(|error| SenderRecoveryError { index, error })
+            })
+            .collect()
+    }
 }
 
 #[cfg(not(feature = "rayon"))]
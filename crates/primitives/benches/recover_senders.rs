@@ -0,0 +1,41 @@
+#![allow(missing_docs)]
+use alloy_consensus::transaction::SignerRecoverable;
+use alloy_primitives::hex_literal::hex;
+use alloy_rlp::Decodable;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_primitives_traits::transaction::recover::recover_senders_par;
+
+/// Number of transactions per block to benchmark, up to roughly a full mainnet block.
+const BLOCK_SIZES: [usize; 3] = [50, 200, 500];
+
+/// Benchmarks serial sender recovery against [`recover_senders_par`].
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let raw = hex!("f88b8212b085028fa6ae00830f424094aad593da0c8116ef7d2d594dd6a63241bccfc26c80a48318b64b000000000000000000000000641c5d790f862a58ec7abcfd644c0442e9c201b32aa0a6ef9e170bca5ffb7ac05433b13b7043de667fbb0b4a5e45d3b54fb2d6efcc63a0037ec2c05c3d60c5f5f78244ce0a3859e3a18a36c61efb061b383507d3ce19d2");
+    let tx = TransactionSigned::decode(&mut raw.as_ref()).unwrap();
+
+    let mut group = c.benchmark_group("recover senders");
+    for size in BLOCK_SIZES {
+        let txs = vec![tx.clone(); size];
+
+        group.bench_with_input(BenchmarkId::new("serial", size), &txs, |b, txs| {
+            b.iter(|| {
+                txs.iter()
+                    .map(SignerRecoverable::recover_signer)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), &txs, |b, txs| {
+            b.iter(|| recover_senders_par::<EthPrimitives>(txs).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
@@ -31,6 +31,10 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use trace::{
+    BlockTraces, TraceCapabilities, TraceLimits, TraceOutputFormat, TraceOverrideSupport,
+};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -1,5 +1,5 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{map::HashSet, Bytes, B256};
+use alloy_primitives::{map::HashSet, Bytes, B256, U64};
 use alloy_rpc_types_eth::{
     state::StateOverride, transaction::TransactionRequest, BlockOverrides, Index,
 };
@@ -9,6 +9,7 @@ use alloy_rpc_types_trace::{
     parity::*,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 
 /// Ethereum trace API
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "trace"))]
@@ -37,15 +38,30 @@ pub trait TraceApi {
 
     /// Traces a call to `eth_sendRawTransaction` without making the call, returning the traces.
     ///
-    /// Expects a raw transaction data
+    /// Expects a raw transaction data. The optional block overrides are applied to the block the
+    /// transaction is traced in.
     #[method(name = "rawTransaction")]
     async fn trace_raw_transaction(
         &self,
         data: Bytes,
         trace_types: HashSet<TraceType>,
         block_id: Option<BlockId>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<TraceResults>;
 
+    /// Traces multiple raw transactions on top of the same block, returning the traces of each
+    /// transaction.
+    ///
+    /// Unlike `trace_callMany`, the transactions do not affect each other: every transaction is
+    /// traced against the state at the given block.
+    #[method(name = "rawTransactionMany")]
+    async fn trace_raw_transaction_many(
+        &self,
+        txs: Vec<Bytes>,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<TraceResults>>;
+
     /// Replays all transactions in a block returning the requested traces for each transaction.
     #[method(name = "replayBlockTransactions")]
     async fn replay_block_transactions(
@@ -69,18 +85,44 @@ pub trait TraceApi {
         block_id: BlockId,
     ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>>;
 
+    /// Returns traces created at each block of the given inclusive range of block numbers, in
+    /// block order.
+    ///
+    /// The range is subject to the same limit as the range of `trace_filter`.
+    #[method(name = "blockRange")]
+    async fn trace_block_range(
+        &self,
+        from_block: U64,
+        to_block: U64,
+    ) -> RpcResult<Vec<BlockTraces>>;
+
     /// Returns traces matching given filter.
     ///
     /// This is similar to `eth_getLogs` but for traces.
     #[method(name = "filter")]
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>>;
 
+    /// Returns the number of traces matching given filter, without returning the traces.
+    ///
+    /// `after` and `count` of the filter are applied, i.e. this is the number of traces
+    /// `trace_filter` would return.
+    #[method(name = "filterCount")]
+    async fn trace_filter_count(&self, filter: TraceFilter) -> RpcResult<U64>;
+
+    /// Returns the distinct hashes of the transactions with a trace matching given filter, in
+    /// block order, without returning the traces.
+    ///
+    /// Reward traces are never matched. `after` and `count` of the filter are applied to the
+    /// transactions.
+    #[method(name = "filterHashes")]
+    async fn trace_filter_hashes(&self, filter: TraceFilter) -> RpcResult<Vec<B256>>;
+
     /// Returns transaction trace at given index.
     ///
     /// `indices` represent the index positions of the traces.
     ///
     /// Note: This expects a list of indices but only one is supported since this function returns a
-    /// single [`LocalizedTransactionTrace`].
+    /// single [`LocalizedTransactionTrace`]. More than one index is rejected as invalid params.
     #[method(name = "get")]
     async fn trace_get(
         &self,
@@ -88,6 +130,17 @@ pub trait TraceApi {
         indices: Vec<Index>,
     ) -> RpcResult<Option<LocalizedTransactionTrace>>;
 
+    /// Returns the transaction traces at the given indices, in the same order as the indices.
+    ///
+    /// Unlike `trace_get`, this supports any number of indices. An entry is `null` if the trace at
+    /// that index does not exist.
+    #[method(name = "getMany")]
+    async fn trace_get_many(
+        &self,
+        hash: B256,
+        indices: Vec<Index>,
+    ) -> RpcResult<Vec<Option<LocalizedTransactionTrace>>>;
+
     /// Returns all traces of given transaction.
     #[method(name = "transaction")]
     async fn trace_transaction(
@@ -108,4 +161,88 @@ pub trait TraceApi {
     /// This is the same as `trace_transactionOpcodeGas` but for all transactions in a block.
     #[method(name = "blockOpcodeGas")]
     async fn trace_block_opcode_gas(&self, block_id: BlockId) -> RpcResult<Option<BlockOpcodeGas>>;
+
+    /// Returns the trace methods, output formats, overrides and limits supported by the node.
+    #[method(name = "capabilities")]
+    async fn trace_capabilities(&self) -> RpcResult<TraceCapabilities>;
+}
+
+/// The tracing capabilities of a node, as returned by `trace_capabilities`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCapabilities {
+    /// The names of the supported `trace` namespace methods.
+    pub methods: Vec<String>,
+    /// The output formats the node can produce.
+    pub output_formats: Vec<TraceOutputFormat>,
+    /// The methods that accept overrides.
+    pub overrides: TraceOverrideSupport,
+    /// The configured limits.
+    pub limits: TraceLimits,
+}
+
+/// The traces of a block, as returned by `trace_blockRange`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTraces {
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The traces created at the block, like the ones returned by `trace_block`.
+    pub traces: Vec<LocalizedTransactionTrace>,
+}
+
+/// An output format of a trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceOutputFormat {
+    /// Parity style traces, including `vmTrace` and `stateDiff`.
+    Parity,
+    /// Flat lists of localized call traces, as returned by `trace_block` and `trace_filter`.
+    FlatCall,
+    /// Geth style struct logs, as returned by `debug_traceTransaction`.
+    StructLog,
+    /// Per opcode counts and gas usage.
+    OpcodeGas,
+    /// Results of custom javascript tracers, as returned by `debug_traceTransaction`.
+    ///
+    /// Only available if the node is compiled with javascript tracer support.
+    Js,
+}
+
+/// The methods that accept state and block overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOverrideSupport {
+    /// The methods that accept state overrides.
+    pub state: Vec<String>,
+    /// The methods that accept block overrides.
+    pub block: Vec<String>,
+}
+
+/// The limits a node applies to tracing requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceLimits {
+    /// The maximum number of blocks a `trace_filter` request can span.
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_trace_filter_blocks: u64,
+    /// The maximum number of tracing requests that are executed concurrently.
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_tracing_requests: u64,
+    /// The gas limit of traced calls.
+    #[serde(with = "alloy_serde::quantity")]
+    pub rpc_gas_cap: u64,
+    /// The maximum number of traces returned by `trace_transaction` and `trace_block`, if limited.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub max_traces_per_response: Option<u64>,
+    /// The maximum number of calls in a `trace_callMany` request, if limited.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub max_trace_call_many: Option<u64>,
+    /// The maximum duration of a single tracing request in milliseconds, if limited.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub trace_call_timeout: Option<u64>,
+    /// The maximum number of blocks traced concurrently by a `trace_filter` request.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub max_trace_filter_concurrency: Option<u64>,
 }
@@ -20,6 +20,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use alloy_primitives::{map::HashSet, Address};
 use alloy_provider::{fillers::RecommendedFillers, Provider, ProviderBuilder};
 use core::marker::PhantomData;
 use error::{ConflictingModules, RpcError, ServerKind};
@@ -57,6 +58,7 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tower_http::cors::CorsLayer;
@@ -400,6 +402,8 @@ pub struct RpcModuleConfig {
     eth: EthConfig,
     /// `flashbots` namespace settings
     flashbots: ValidationApiConfig,
+    /// Addresses the `trace` namespace is restricted to, if any
+    trace_address_allowlist: Option<HashSet<Address>>,
 }
 
 // === impl RpcModuleConfig ===
@@ -412,7 +416,7 @@ impl RpcModuleConfig {
 
     /// Returns a new RPC module config given the eth namespace config
     pub const fn new(eth: EthConfig, flashbots: ValidationApiConfig) -> Self {
-        Self { eth, flashbots }
+        Self { eth, flashbots, trace_address_allowlist: None }
     }
 
     /// Get a reference to the eth namespace config
@@ -424,6 +428,17 @@ impl RpcModuleConfig {
     pub const fn eth_mut(&mut self) -> &mut EthConfig {
         &mut self.eth
     }
+
+    /// Get a reference to the addresses the `trace` namespace is restricted to, if any
+    pub const fn trace_address_allowlist(&self) -> Option<&HashSet<Address>> {
+        self.trace_address_allowlist.as_ref()
+    }
+
+    /// Restricts the `trace` namespace to the given addresses, see
+    /// [`TraceApiBuilder::address_allowlist`](reth_rpc::TraceApiBuilder::address_allowlist)
+    pub fn set_trace_address_allowlist(&mut self, addresses: HashSet<Address>) {
+        self.trace_address_allowlist = Some(addresses);
+    }
 }
 
 /// Configures [`RpcModuleConfig`]
@@ -431,6 +446,7 @@ impl RpcModuleConfig {
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
     flashbots: Option<ValidationApiConfig>,
+    trace_address_allowlist: Option<HashSet<Address>>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -448,10 +464,21 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Restricts the `trace` namespace to the given addresses, see
+    /// [`TraceApiBuilder::address_allowlist`](reth_rpc::TraceApiBuilder::address_allowlist)
+    pub fn trace_address_allowlist(mut self, addresses: HashSet<Address>) -> Self {
+        self.trace_address_allowlist = Some(addresses);
+        self
+    }
+
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
-        let Self { eth, flashbots } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default(), flashbots: flashbots.unwrap_or_default() }
+        let Self { eth, flashbots, trace_address_allowlist } = self;
+        RpcModuleConfig {
+            eth: eth.unwrap_or_default(),
+            flashbots: flashbots.unwrap_or_default(),
+            trace_address_allowlist,
+        }
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -495,6 +522,8 @@ pub struct RpcRegistryInner<
     modules: HashMap<RethRpcModule, Methods>,
     /// eth config settings
     eth_config: EthConfig,
+    /// addresses the `trace` namespace is restricted to
+    trace_address_allowlist: Option<Arc<HashSet<Address>>>,
 }
 
 // === impl RpcRegistryInner ===
@@ -543,6 +572,7 @@ where
             modules: Default::default(),
             blocking_pool_guard,
             eth_config: config.eth,
+            trace_address_allowlist: config.trace_address_allowlist.map(Arc::new),
             evm_config,
         }
     }
@@ -773,7 +803,12 @@ where
     where
         EthApi: TraceExt,
     {
-        TraceApi::new(self.eth_api().clone(), self.blocking_pool_guard.clone(), self.eth_config)
+        build_trace_api(
+            self.eth_api().clone(),
+            self.blocking_pool_guard.clone(),
+            self.eth_config,
+            self.trace_address_allowlist.clone(),
+        )
     }
 
     /// Instantiates [`EthBundle`] Api
@@ -947,10 +982,11 @@ where
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Trace => TraceApi::new(
+                        RethRpcModule::Trace => build_trace_api(
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                             self.eth_config,
+                            self.trace_address_allowlist.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -992,6 +1028,22 @@ where
     }
 }
 
+/// Returns a [`TraceApi`] for the given `eth` API, restricted to the given addresses if any.
+fn build_trace_api<EthApi>(
+    eth_api: EthApi,
+    blocking_task_guard: BlockingTaskGuard,
+    eth_config: EthConfig,
+    address_allowlist: Option<Arc<HashSet<Address>>>,
+) -> TraceApi<EthApi> {
+    let builder =
+        TraceApi::builder(eth_api).blocking_task_guard(blocking_task_guard).eth_config(eth_config);
+    match address_allowlist {
+        Some(addresses) => builder.address_allowlist(addresses),
+        None => builder,
+    }
+    .build()
+}
+
 /// A builder type for configuring and launching the servers that will handle RPC requests.
 ///
 /// Supported server transports are:
@@ -1,7 +1,7 @@
 #![allow(unreachable_pub)]
 //! Standalone http tests
 
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_ws, test_address, test_rpc_builder};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId, BlockNumberOrTag};
 use alloy_primitives::{hex_literal::hex, Address, Bytes, TxHash, B256, B64, U256, U64};
 use alloy_rpc_types_eth::{
@@ -25,6 +25,7 @@ use reth_rpc_api::{
     DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, NetApiClient, OtterscanClient,
     TraceApiClient, Web3ApiClient,
 };
+use reth_rpc_builder::{RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_server_types::RethRpcModule;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
@@ -441,9 +442,17 @@ where
         count: None,
     };
 
-    TraceApiClient::trace_raw_transaction(client, Bytes::default(), HashSet::default(), None)
+    TraceApiClient::trace_raw_transaction(client, Bytes::default(), HashSet::default(), None, None)
         .await
         .unwrap_err();
+    TraceApiClient::trace_raw_transaction_many(
+        client,
+        vec![Bytes::default()],
+        HashSet::default(),
+        None,
+    )
+    .await
+    .unwrap_err();
     TraceApiClient::trace_call_many(client, vec![], Some(BlockNumberOrTag::Latest.into()))
         .await
         .unwrap_err();
@@ -452,10 +461,15 @@ where
         .err()
         .unwrap();
     TraceApiClient::trace_block(client, block_id).await.unwrap_err();
+    TraceApiClient::trace_block_range(client, U64::ZERO, U64::ZERO).await.unwrap_err();
     TraceApiClient::replay_block_transactions(client, block_id, HashSet::default())
         .await
         .unwrap_err();
 
+    TraceApiClient::trace_get_many(client, B256::default(), vec![Index::from(0)]).await.unwrap();
+
+    TraceApiClient::trace_filter_count(client, trace_filter.clone()).await.unwrap();
+    TraceApiClient::trace_filter_hashes(client, trace_filter.clone()).await.unwrap();
     TraceApiClient::trace_filter(client, trace_filter).await.unwrap();
 }
 
@@ -702,6 +716,68 @@ async fn test_call_trace_functions_http_and_ws() {
     test_basic_trace_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trace_call_many_rejects_oversized_batch() {
+    reth_tracing::init_test_tracing();
+
+    let mut config = RpcModuleConfig::default();
+    config.eth_mut().max_trace_call_many = Some(1);
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Trace]).with_config(config),
+        eth_api,
+    );
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&server)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    // the noop provider has no state to execute the calls on, so the batch can only fail with the
+    // limit error if it's rejected before execution
+    let calls = vec![(TransactionRequest::default(), HashSet::default()); 2];
+    let err =
+        TraceApiClient::trace_call_many(&client, calls, Some(BlockNumberOrTag::Latest.into()))
+            .await
+            .unwrap_err();
+    assert!(err.to_string().contains("Too many calls: 2 calls requested, limited to 1 calls"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trace_call_rejects_address_outside_allowlist() {
+    reth_tracing::init_test_tracing();
+
+    let allowed = Address::with_last_byte(1);
+    let config = RpcModuleConfig::builder()
+        .trace_address_allowlist(std::iter::once(allowed).collect())
+        .build();
+    let builder = test_rpc_builder();
+    let eth_api = builder.bootstrap_eth_api();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Trace]).with_config(config),
+        eth_api,
+    );
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&server)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    let disallowed = Address::with_last_byte(2);
+    let call = TransactionRequest::default().to(disallowed);
+    let err = TraceApiClient::trace_call_many(
+        &client,
+        vec![(call, HashSet::default())],
+        Some(BlockNumberOrTag::Latest.into()),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains(&format!("address {disallowed} is not allowed to be traced")));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_web3_functions_http() {
     reth_tracing::init_test_tracing();
@@ -19,8 +19,8 @@ pub use block::TryFromBlockResponse;
 pub use fees::{CallFees, CallFeesError};
 pub use rpc::*;
 pub use transaction::{
-    EthTxEnvError, IntoRpcTx, RpcConvert, RpcConverter, TransactionConversionError, TryIntoSimTx,
-    TxInfoMapper,
+    tx_to_request, EthTxEnvError, IntoRpcTx, RpcConvert, RpcConverter, TransactionConversionError,
+    TryIntoSimTx, TxInfoMapper,
 };
 
 #[cfg(feature = "op")]
@@ -187,6 +187,15 @@ impl TryIntoSimTx<EthereumTxEnvelope<TxEip4844>> for TransactionRequest {
     }
 }
 
+/// Converts the given [`NodePrimitives`] transaction sent by `sender` back into a
+/// [`TransactionRequest`], e.g. to simulate a mined transaction with modifications.
+///
+/// The request carries the recipient, value, input, gas limit, fees, nonce, chain id, access list,
+/// blob fields and authorization list of the transaction, but no signature.
+pub fn tx_to_request<N: NodePrimitives>(tx: &TxTy<N>, sender: Address) -> TransactionRequest {
+    TransactionRequest::from_transaction_with_sender(tx.clone(), sender)
+}
+
 /// Converts `self` into `T`.
 ///
 /// Should create an executable transaction environment using [`TransactionRequest`].
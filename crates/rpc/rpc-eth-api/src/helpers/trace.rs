@@ -12,8 +12,9 @@ use reth_evm::{
     evm::EvmFactoryExt, system_calls::SystemCaller, tracing::TracingCtx, ConfigureEvm, Database,
     Evm, EvmEnvFor, EvmFor, HaltReasonFor, InspectorFor, TxEnvFor,
 };
-use reth_node_api::NodePrimitives;
-use reth_primitives_traits::{BlockBody, Recovered, RecoveredBlock, SignedTransaction};
+use reth_primitives_traits::{
+    BlockBody, PooledNodePrimitives, Recovered, RecoveredBlock, SignedTransaction,
+};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
@@ -25,12 +26,16 @@ use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
 use std::sync::Arc;
 
 /// Executes CPU heavy tasks.
+///
+/// The primitives of the EVM must have a pooled transaction type, see [`PooledNodePrimitives`], so
+/// that raw transactions can be decoded for tracing.
 pub trait Trace:
     LoadState<
     Provider: BlockReader,
     Evm: ConfigureEvm<
-        Primitives: NodePrimitives<
+        Primitives: PooledNodePrimitives<
             BlockHeader = ProviderHeader<Self::Provider>,
+            OmmerHeader = ProviderHeader<Self::Provider>,
             SignedTx = ProviderTx<Self::Provider>,
         >,
     >,
@@ -6,6 +6,7 @@ use crate::{
     EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
+    default_max_trace_call_many_concurrency, default_max_trace_filter_concurrency,
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
     DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
     DEFAULT_PROOF_PERMITS,
@@ -28,6 +29,36 @@ pub struct EthConfig {
     pub max_tracing_requests: usize,
     /// Maximum number of blocks for `trace_filter` requests.
     pub max_trace_filter_blocks: u64,
+    /// Maximum number of blocks that are traced concurrently by a single `trace_filter` request.
+    ///
+    /// This is independent of [`Self::max_tracing_requests`], which limits the number of
+    /// concurrent tracing requests.
+    pub max_trace_filter_concurrency: usize,
+    /// Whether block and uncle reward traces are included in `trace_block` and `trace_filter`
+    /// responses.
+    pub include_reward_traces: bool,
+    /// Maximum number of calls in a single `trace_callMany` request.
+    ///
+    /// If `None` then the number of calls is not limited.
+    pub max_trace_call_many: Option<usize>,
+    /// Maximum number of calls that are traced concurrently by a single batch of parallel call
+    /// traces.
+    ///
+    /// This is independent of [`Self::max_tracing_requests`], since such a batch holds a single
+    /// tracing permit.
+    pub max_trace_call_many_concurrency: usize,
+    /// Maximum duration of a single `trace` namespace call.
+    ///
+    /// If `None` then no timeout is enforced.
+    pub trace_call_timeout: Option<Duration>,
+    /// Maximum number of transactions whose `trace_transaction` traces are cached.
+    ///
+    /// If `0` then no traces are cached.
+    pub trace_transaction_cache_size: u32,
+    /// Maximum number of traces returned by `trace_transaction` and `trace_block`.
+    ///
+    /// If `None` then the number of traces is not limited.
+    pub max_traces_per_response: Option<usize>,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
@@ -65,6 +96,13 @@ impl Default for EthConfig {
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
             max_tracing_requests: default_max_tracing_requests(),
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_trace_filter_concurrency: default_max_trace_filter_concurrency(),
+            include_reward_traces: true,
+            max_trace_call_many: None,
+            max_trace_call_many_concurrency: default_max_trace_call_many_concurrency(),
+            trace_call_timeout: None,
+            trace_transaction_cache_size: 0,
+            max_traces_per_response: None,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
@@ -107,6 +145,50 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of blocks traced concurrently by a `trace_filter` request
+    pub const fn max_trace_filter_concurrency(mut self, max_blocks: usize) -> Self {
+        self.max_trace_filter_concurrency = max_blocks;
+        self
+    }
+
+    /// Configures whether reward traces are included in `trace_block` and `trace_filter`
+    /// responses
+    pub const fn include_reward_traces(mut self, include: bool) -> Self {
+        self.include_reward_traces = include;
+        self
+    }
+
+    /// Configures the maximum number of calls in a `trace_callMany` request
+    pub const fn max_trace_call_many(mut self, max_calls: usize) -> Self {
+        self.max_trace_call_many = Some(max_calls);
+        self
+    }
+
+    /// Configures the maximum number of calls traced concurrently by a batch of parallel call
+    /// traces
+    pub const fn max_trace_call_many_concurrency(mut self, max_calls: usize) -> Self {
+        self.max_trace_call_many_concurrency = max_calls;
+        self
+    }
+
+    /// Configures the maximum duration of a single `trace` namespace call
+    pub const fn trace_call_timeout(mut self, timeout: Duration) -> Self {
+        self.trace_call_timeout = Some(timeout);
+        self
+    }
+
+    /// Configures the maximum number of transactions whose `trace_transaction` traces are cached
+    pub const fn trace_transaction_cache_size(mut self, size: u32) -> Self {
+        self.trace_transaction_cache_size = size;
+        self
+    }
+
+    /// Configures the maximum number of traces returned by `trace_transaction` and `trace_block`
+    pub const fn max_traces_per_response(mut self, max_traces: usize) -> Self {
+        self.max_traces_per_response = Some(max_traces);
+        self
+    }
+
     /// Configures the maximum number of logs per response
     pub const fn max_logs_per_response(mut self, max_logs: usize) -> Self {
         self.max_logs_per_response = max_logs;
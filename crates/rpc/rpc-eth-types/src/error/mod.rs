@@ -187,6 +187,19 @@ impl EthApiError {
         matches!(self, Self::InvalidTransaction(RpcInvalidTransactionError::GasTooLow))
     }
 
+    /// Returns `true` if error is any of the out of gas variants of [`RpcInvalidTransactionError`]
+    pub const fn is_out_of_gas(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidTransaction(
+                RpcInvalidTransactionError::BasicOutOfGas(_) |
+                    RpcInvalidTransactionError::MemoryOutOfGas(_) |
+                    RpcInvalidTransactionError::PrecompileOutOfGas(_) |
+                    RpcInvalidTransactionError::InvalidOperandOutOfGas(_)
+            )
+        )
+    }
+
     /// Returns the [`RpcInvalidTransactionError`] if this is a [`EthApiError::InvalidTransaction`]
     pub const fn as_invalid_transaction(&self) -> Option<&RpcInvalidTransactionError> {
         match self {
@@ -998,6 +1011,16 @@ mod tests {
         assert_eq!(err.to_string(), "execution aborted (timeout = 10s)");
     }
 
+    #[test]
+    fn out_of_gas_errors() {
+        let err = EthApiError::from_evm_halt(HaltReason::OutOfGas(OutOfGasError::Memory), 100);
+        assert!(err.is_out_of_gas());
+        assert!(!EthApiError::from_evm_halt(HaltReason::OpcodeNotFound, 100).is_out_of_gas());
+        assert!(
+            !EthApiError::InvalidTransaction(RpcInvalidTransactionError::GasTooLow).is_out_of_gas()
+        );
+    }
+
     #[test]
     fn header_not_found_message() {
         let err: jsonrpsee_types::error::ErrorObject<'static> =
@@ -29,6 +29,18 @@ pub fn default_max_tracing_requests() -> usize {
         .map_or(25, |cpus| max(cpus.get().saturating_sub(RESERVED), RESERVED))
 }
 
+/// The default maximum number of blocks that are traced concurrently by a single `trace_filter`
+/// request.
+pub fn default_max_trace_filter_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(8, |cpus| cpus.get())
+}
+
+/// The default maximum number of calls that are traced concurrently by a single batch of parallel
+/// call traces.
+pub fn default_max_trace_call_many_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(8, |cpus| cpus.get())
+}
+
 /// The default number of getproof calls we are allowing to run concurrently.
 pub const DEFAULT_PROOF_PERMITS: usize = 25;
 
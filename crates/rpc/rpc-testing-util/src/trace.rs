@@ -334,7 +334,7 @@ impl<T: TraceApiClient + Sync> TraceApiExt for T {
         block_id: Option<BlockId>,
     ) -> RawTransactionTraceStream<'_> {
         let stream = futures::stream::once(async move {
-            match self.trace_raw_transaction(data.clone(), trace_types, block_id).await {
+            match self.trace_raw_transaction(data.clone(), trace_types, block_id, None).await {
                 Ok(result) => Ok((result, data)),
                 Err(err) => Err((err, data)),
             }
@@ -1,7 +1,7 @@
 //! Contains RPC handler implementations specific to tracing.
 
 use reth_evm::ConfigureEvm;
-use reth_node_api::NodePrimitives;
+use reth_primitives_traits::PooledNodePrimitives;
 use reth_rpc_eth_api::{
     helpers::{LoadState, Trace},
     FromEvmError,
@@ -15,7 +15,7 @@ where
     Self: LoadState<
         Provider: BlockReader,
         Evm: ConfigureEvm<
-            Primitives: NodePrimitives<
+            Primitives: PooledNodePrimitives<
                 BlockHeader = ProviderHeader<Self::Provider>,
                 SignedTx = ProviderTx<Self::Provider>,
             >,
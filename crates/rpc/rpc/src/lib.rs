@@ -55,7 +55,20 @@ pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
 pub use rpc::RPCApi;
-pub use trace::TraceApi;
+#[cfg(feature = "opentelemetry")]
+pub use trace::trace_to_spans;
+pub use trace::{
+    AccountSnapshot, AuthorizationOutcome, AuthorizationRejection, BlockRewardProvider,
+    BlockTraceFrame, CallDelegation, CallTreeNode, CustomTraceInspector,
+    CustomTraceInspectorFactory, DecodedEvent, EthereumBlockRewards, GasInfo, GasSplit,
+    OpcodeCategory, OpcodeCategoryGas, Precompile, PrecompileCall, RevertedTrace, StorageDiff,
+    TraceActionKind, TraceApi, TraceApiBuilder, TraceBlockOptions, TraceCallLog,
+    TraceCallManyOptions, TraceCallManyResults, TraceCallOptions, TraceCallResults, TraceDiff,
+    TraceFilterOptions, TraceFilterOrder, TraceFilterPagination, TraceFrameDiff,
+    TraceReceiptMismatch, TraceTransactionOptions, TransactionOpcodeGasByAddress,
+    TransactionOpcodeGasCategorized, TransactionTraceFrame, TransactionTraceWithLogs,
+    TransactionTracesWithReceipt, TRACE_BLOCK_STREAM_BUFFER,
+};
 pub use txpool::TxPoolApi;
 pub use validation::{ValidationApi, ValidationApiConfig};
 pub use web3::Web3Api;
@@ -0,0 +1,257 @@
+//! EIP-7702 authorization list diagnostics for `trace_call`.
+
+use alloy_eips::eip7702::SignedAuthorization;
+use alloy_primitives::{map::HashMap, Address, Bytes, U256};
+use alloy_rpc_types_eth::transaction::TransactionRequest;
+use reth_primitives_traits::crypto::secp256k1::recover_signer;
+use reth_rpc_eth_types::error::EthApiError;
+use revm::{bytecode::Bytecode, Database};
+use serde::{Deserialize, Serialize};
+
+/// The outcome of a single authorization of an EIP-7702 authorization list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationOutcome {
+    /// The recovered authority, `None` if the signature is invalid.
+    pub authority: Option<Address>,
+    /// The reason the authorization was skipped, `None` if it was applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<AuthorizationRejection>,
+    /// The code of the authority once the whole authorization list has been processed.
+    ///
+    /// This is the EIP-7702 delegation designator of the authority, or `None` if the authority is
+    /// unknown or doesn't delegate.
+    pub delegated_code: Option<Bytes>,
+}
+
+impl AuthorizationOutcome {
+    /// Returns `true` if the authorization was applied.
+    pub const fn is_applied(&self) -> bool {
+        self.rejection.is_none()
+    }
+}
+
+/// Reasons for an authorization to be skipped, see
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702#behavior).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuthorizationRejection {
+    /// The chain id is neither 0 nor the chain id of the simulation.
+    InvalidChainId,
+    /// The nonce is `u64::MAX`.
+    NonceOverflow,
+    /// The signature is malformed or the authority can't be recovered.
+    InvalidSignature,
+    /// The authority has code that is not a delegation designator.
+    AuthorityHasCode,
+    /// The nonce doesn't match the nonce of the authority.
+    NonceMismatch {
+        /// The nonce of the authority at the time the authorization is processed.
+        expected: u64,
+        /// The nonce of the authorization.
+        got: u64,
+    },
+}
+
+/// Validates the shape of the authorization list of the given call, if any.
+///
+/// A call that carries an authorization list must have a non-empty list, must not be a contract
+/// creation and must not carry blob versioned hashes.
+pub(super) fn validate_authorization_list(call: &TransactionRequest) -> Result<(), EthApiError> {
+    let Some(authorization_list) = &call.authorization_list else { return Ok(()) };
+    if authorization_list.is_empty() {
+        return Err(EthApiError::InvalidParams(
+            "invalid authorization list: list is empty".to_string(),
+        ))
+    }
+    if call.to.is_none_or(|to| to.is_create()) {
+        return Err(EthApiError::InvalidParams(
+            "invalid authorization list: set code transactions can't create contracts".to_string(),
+        ))
+    }
+    if call.blob_versioned_hashes.as_ref().is_some_and(|hashes| !hashes.is_empty()) {
+        return Err(EthApiError::InvalidParams(
+            "invalid authorization list: set code transactions can't carry blobs".to_string(),
+        ))
+    }
+    Ok(())
+}
+
+/// Processes the given authorization list against the state of the database, as it would be
+/// applied before executing a call from `caller` on chain `chain_id`.
+///
+/// The database isn't modified.
+pub(super) fn authorization_outcomes<DB: Database>(
+    db: &mut DB,
+    chain_id: u64,
+    caller: Address,
+    authorization_list: &[SignedAuthorization],
+) -> Result<Vec<AuthorizationOutcome>, DB::Error> {
+    // nonce and delegation of all authorities touched so far
+    let mut authorities: HashMap<Address, (u64, Option<Address>)> = HashMap::default();
+    let mut outcomes = Vec::with_capacity(authorization_list.len());
+
+    for authorization in authorization_list {
+        let mut outcome = AuthorizationOutcome {
+            authority: None,
+            rejection: Some(AuthorizationRejection::InvalidChainId),
+            delegated_code: None,
+        };
+
+        let chain_id_matches =
+            authorization.chain_id.is_zero() || authorization.chain_id == U256::from(chain_id);
+        if !chain_id_matches {
+            outcomes.push(outcome);
+            continue
+        }
+        if authorization.nonce == u64::MAX {
+            outcome.rejection = Some(AuthorizationRejection::NonceOverflow);
+            outcomes.push(outcome);
+            continue
+        }
+
+        let authority = authorization
+            .signature()
+            .ok()
+            .and_then(|signature| recover_signer(&signature, authorization.signature_hash()).ok());
+        let Some(authority) = authority else {
+            outcome.rejection = Some(AuthorizationRejection::InvalidSignature);
+            outcomes.push(outcome);
+            continue
+        };
+        outcome.authority = Some(authority);
+
+        let nonce = match authorities.get(&authority) {
+            Some((nonce, _)) => *nonce,
+            None => {
+                let info = db.basic(authority)?.unwrap_or_default();
+                let code = match info.code {
+                    Some(code) => code,
+                    None => db.code_by_hash(info.code_hash)?,
+                };
+                let delegation = match code {
+                    Bytecode::Eip7702(code) => Some(code.address()),
+                    code if code.is_empty() => None,
+                    _ => {
+                        outcome.rejection = Some(AuthorizationRejection::AuthorityHasCode);
+                        outcomes.push(outcome);
+                        continue
+                    }
+                };
+                // the caller's nonce is bumped before the authorization list is processed
+                let nonce = if authority == caller { info.nonce + 1 } else { info.nonce };
+                authorities.insert(authority, (nonce, delegation));
+                nonce
+            }
+        };
+
+        if authorization.nonce != nonce {
+            outcome.rejection = Some(AuthorizationRejection::NonceMismatch {
+                expected: nonce,
+                got: authorization.nonce,
+            });
+            outcomes.push(outcome);
+            continue
+        }
+
+        let delegation = (!authorization.address.is_zero()).then_some(authorization.address);
+        authorities.insert(authority, (nonce + 1, delegation));
+        outcome.rejection = None;
+        outcomes.push(outcome);
+    }
+
+    for outcome in &mut outcomes {
+        let Some(authority) = outcome.authority else { continue };
+        outcome.delegated_code = authorities
+            .get(&authority)
+            .and_then(|(_, delegation)| *delegation)
+            .map(|address| Bytecode::new_eip7702(address).original_bytes());
+    }
+
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip7702::Authorization;
+    use alloy_primitives::{address, TxKind};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::state::AccountInfo;
+
+    fn sign(signer: &PrivateKeySigner, chain_id: u64, nonce: u64) -> SignedAuthorization {
+        let authorization = Authorization {
+            chain_id: U256::from(chain_id),
+            address: address!("0x00000000000000000000000000000000000000de"),
+            nonce,
+        };
+        let signature = signer.sign_hash_sync(&authorization.signature_hash()).unwrap();
+        authorization.into_signed(signature)
+    }
+
+    #[test]
+    fn validates_authorization_list() {
+        let authorization = sign(&PrivateKeySigner::random(), 1, 0);
+        let call = TransactionRequest {
+            to: Some(TxKind::Call(Address::ZERO)),
+            authorization_list: Some(vec![authorization]),
+            ..Default::default()
+        };
+        validate_authorization_list(&call).unwrap();
+        validate_authorization_list(&TransactionRequest::default()).unwrap();
+
+        let empty = TransactionRequest { authorization_list: Some(vec![]), ..call.clone() };
+        assert!(matches!(validate_authorization_list(&empty), Err(EthApiError::InvalidParams(_))));
+
+        let create = TransactionRequest { to: Some(TxKind::Create), ..call };
+        assert!(matches!(validate_authorization_list(&create), Err(EthApiError::InvalidParams(_))));
+    }
+
+    #[test]
+    fn reports_authorization_outcomes() {
+        let caller = PrivateKeySigner::random();
+        let authority = PrivateKeySigner::random();
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(authority.address(), AccountInfo { nonce: 3, ..Default::default() });
+
+        let invalid_signature = SignedAuthorization::new_unchecked(
+            sign(&authority, 1, 3).strip_signature(),
+            0,
+            U256::ZERO,
+            U256::ZERO,
+        );
+        let list = vec![
+            sign(&authority, 2, 3),
+            sign(&authority, 1, 2),
+            invalid_signature,
+            sign(&authority, 0, 3),
+            // the caller's nonce is bumped before the list is processed
+            sign(&caller, 1, 1),
+            // nonce was bumped by the previous authorization of the same authority
+            sign(&authority, 1, 4),
+        ];
+
+        let outcomes = authorization_outcomes(&mut db, 1, caller.address(), &list).unwrap();
+        let rejections = outcomes.iter().map(|outcome| outcome.rejection).collect::<Vec<_>>();
+        assert_eq!(
+            rejections,
+            vec![
+                Some(AuthorizationRejection::InvalidChainId),
+                Some(AuthorizationRejection::NonceMismatch { expected: 3, got: 2 }),
+                Some(AuthorizationRejection::InvalidSignature),
+                None,
+                None,
+                None,
+            ]
+        );
+        assert_eq!(outcomes[1].authority, Some(authority.address()));
+        assert_eq!(outcomes[4].authority, Some(caller.address()));
+
+        let delegated_code = Bytecode::new_eip7702(list[0].address).original_bytes();
+        assert_eq!(outcomes[1].delegated_code, Some(delegated_code.clone()));
+        assert_eq!(outcomes[4].delegated_code, Some(delegated_code));
+        assert_eq!(outcomes[2].delegated_code, None);
+    }
+}
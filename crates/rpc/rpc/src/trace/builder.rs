@@ -0,0 +1,117 @@
+//! `TraceApiBuilder` implementation
+
+use super::{
+    cache::TransactionTraceCache, BlockRewardProvider, CustomTraceInspectorFactory, TraceApi,
+    TraceApiInner, TraceIndexProvider,
+};
+use alloy_primitives::{map::HashSet, Address};
+use reth_rpc_eth_types::EthConfig;
+use reth_tasks::pool::BlockingTaskGuard;
+use std::sync::Arc;
+
+/// A helper to build the [`TraceApi`] handler instance.
+///
+/// Settings that aren't configured explicitly fall back to the defaults of [`EthConfig`].
+pub struct TraceApiBuilder<Eth> {
+    eth_api: Eth,
+    eth_config: EthConfig,
+    blocking_task_guard: Option<BlockingTaskGuard>,
+    custom_inspector: Option<CustomTraceInspectorFactory>,
+    trace_index: Option<Arc<dyn TraceIndexProvider>>,
+    block_rewards: Option<Arc<dyn BlockRewardProvider>>,
+    address_allowlist: Option<Arc<HashSet<Address>>>,
+}
+
+impl<Eth> std::fmt::Debug for TraceApiBuilder<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TraceApiBuilder")
+            .field("eth_config", &self.eth_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Eth> TraceApiBuilder<Eth> {
+    /// Creates a new `TraceApiBuilder` instance.
+    pub fn new(eth_api: Eth) -> Self {
+        Self {
+            eth_api,
+            eth_config: EthConfig::default(),
+            blocking_task_guard: None,
+            custom_inspector: None,
+            trace_index: None,
+            block_rewards: None,
+            address_allowlist: None,
+        }
+    }
+
+    /// Sets the [`EthConfig`] settings of the `trace` namespace.
+    pub const fn eth_config(mut self, eth_config: EthConfig) -> Self {
+        self.eth_config = eth_config;
+        self
+    }
+
+    /// Sets the guard that restricts the number of concurrent tracing calls.
+    ///
+    /// If not set, a new guard allowing [`EthConfig::max_tracing_requests`] concurrent calls is
+    /// created.
+    pub fn blocking_task_guard(mut self, blocking_task_guard: BlockingTaskGuard) -> Self {
+        self.blocking_task_guard = Some(blocking_task_guard);
+        self
+    }
+
+    /// Sets the [`CustomTraceInspector`](super::CustomTraceInspector) factory, see
+    /// [`TraceApi::register_custom_inspector`].
+    pub fn custom_inspector(mut self, factory: CustomTraceInspectorFactory) -> Self {
+        self.custom_inspector = Some(factory);
+        self
+    }
+
+    /// Sets the [`TraceIndexProvider`], see [`TraceApi::register_trace_index`].
+    pub fn trace_index(mut self, trace_index: Arc<dyn TraceIndexProvider>) -> Self {
+        self.trace_index = Some(trace_index);
+        self
+    }
+
+    /// Sets the [`BlockRewardProvider`], see [`TraceApi::register_block_rewards`].
+    pub fn block_rewards(mut self, block_rewards: Arc<dyn BlockRewardProvider>) -> Self {
+        self.block_rewards = Some(block_rewards);
+        self
+    }
+
+    /// Restricts tracing to the given addresses, by default tracing isn't restricted.
+    ///
+    /// This allows exposing the `trace` namespace for the contracts of a specific application:
+    /// - `trace_filter` only accepts filters whose `fromAddress` and `toAddress` are restricted to
+    ///   allowed addresses, and only returns traces involving an allowed address.
+    /// - `trace_call` and `trace_callMany` only accept calls to allowed addresses.
+    pub fn address_allowlist(mut self, addresses: impl Into<Arc<HashSet<Address>>>) -> Self {
+        self.address_allowlist = Some(addresses.into());
+        self
+    }
+
+    /// Builds the [`TraceApi`] instance.
+    pub fn build(self) -> TraceApi<Eth> {
+        let Self {
+            eth_api,
+            eth_config,
+            blocking_task_guard,
+            custom_inspector,
+            trace_index,
+            block_rewards,
+            address_allowlist,
+        } = self;
+        let blocking_task_guard = blocking_task_guard
+            .unwrap_or_else(|| BlockingTaskGuard::new(eth_config.max_tracing_requests));
+        let inner = Arc::new(TraceApiInner {
+            eth_api,
+            blocking_task_guard,
+            custom_inspector: custom_inspector.into(),
+            trace_index: trace_index.into(),
+            block_rewards: block_rewards.into(),
+            address_allowlist,
+            transaction_traces: TransactionTraceCache::new(eth_config.trace_transaction_cache_size),
+            eth_config,
+        });
+        TraceApi { inner }
+    }
+}
@@ -0,0 +1,74 @@
+//! Cache of the traces returned by `trace_transaction`.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::parity::LocalizedTransactionTrace;
+use parking_lot::Mutex;
+use schnellru::{ByLength, LruMap};
+
+/// A LRU cache of the traces of transactions, keyed by the hash of the transaction and the hash of
+/// the block it's included in.
+///
+/// Keying by the block hash ensures that a transaction that has been reorged into another block
+/// is traced again rather than served from the cache.
+pub(super) struct TransactionTraceCache {
+    traces: Mutex<LruMap<(B256, B256), Vec<LocalizedTransactionTrace>, ByLength>>,
+}
+
+impl TransactionTraceCache {
+    /// Creates a new cache holding the traces of at most `capacity` transactions, or `None` if the
+    /// capacity is 0.
+    pub(super) fn new(capacity: u32) -> Option<Self> {
+        (capacity > 0).then(|| Self { traces: Mutex::new(LruMap::new(ByLength::new(capacity))) })
+    }
+
+    /// Returns the cached traces of the transaction included in the given block.
+    pub(super) fn get(
+        &self,
+        tx_hash: B256,
+        block_hash: B256,
+    ) -> Option<Vec<LocalizedTransactionTrace>> {
+        self.traces.lock().get(&(tx_hash, block_hash)).cloned()
+    }
+
+    /// Caches the traces of the transaction included in the given block.
+    pub(super) fn insert(
+        &self,
+        tx_hash: B256,
+        block_hash: B256,
+        traces: Vec<LocalizedTransactionTrace>,
+    ) {
+        self.traces.lock().insert((tx_hash, block_hash), traces);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::{call_trace, localized};
+
+    fn traces(block_hash: B256) -> Vec<LocalizedTransactionTrace> {
+        vec![LocalizedTransactionTrace {
+            block_hash: Some(block_hash),
+            block_number: Some(1),
+            transaction_position: Some(0),
+            ..localized(call_trace(vec![]))
+        }]
+    }
+
+    #[test]
+    fn caches_traces_by_transaction_and_block() {
+        assert!(TransactionTraceCache::new(0).is_none());
+
+        let cache = TransactionTraceCache::new(1).unwrap();
+        let (tx, block, reorged) =
+            (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+        cache.insert(tx, block, traces(block));
+        assert_eq!(cache.get(tx, block), Some(traces(block)));
+        assert_eq!(cache.get(tx, reorged), None);
+
+        // evicts the least recently used transaction
+        cache.insert(tx, reorged, traces(reorged));
+        assert_eq!(cache.get(tx, block), None);
+        assert_eq!(cache.get(tx, reorged), Some(traces(reorged)));
+    }
+}
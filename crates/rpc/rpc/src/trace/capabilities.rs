@@ -0,0 +1,85 @@
+//! Description of the tracing capabilities of the node for `trace_capabilities`.
+
+use reth_rpc_api::{TraceCapabilities, TraceLimits, TraceOutputFormat, TraceOverrideSupport};
+use reth_rpc_eth_types::EthConfig;
+
+/// Returns the capabilities of a node that serves the given `trace` methods with the given
+/// config.
+pub(super) fn trace_capabilities<'a>(
+    methods: impl IntoIterator<Item = &'a str>,
+    config: &EthConfig,
+) -> TraceCapabilities {
+    let mut methods = methods.into_iter().map(String::from).collect::<Vec<_>>();
+    methods.sort_unstable();
+
+    let mut output_formats = vec![
+        TraceOutputFormat::Parity,
+        TraceOutputFormat::FlatCall,
+        TraceOutputFormat::StructLog,
+        TraceOutputFormat::OpcodeGas,
+    ];
+    if cfg!(feature = "js-tracer") {
+        output_formats.push(TraceOutputFormat::Js);
+    }
+
+    TraceCapabilities {
+        methods,
+        output_formats,
+        overrides: TraceOverrideSupport {
+            state: vec!["trace_call".to_string()],
+            block: vec!["trace_call".to_string(), "trace_rawTransaction".to_string()],
+        },
+        limits: TraceLimits {
+            max_trace_filter_blocks: config.max_trace_filter_blocks,
+            max_tracing_requests: config.max_tracing_requests as u64,
+            rpc_gas_cap: config.rpc_gas_cap,
+            max_traces_per_response: config.max_traces_per_response.map(|max| max as u64),
+            max_trace_call_many: config.max_trace_call_many.map(|max| max as u64),
+            trace_call_timeout: config
+                .trace_call_timeout
+                .map(|timeout| timeout.as_millis().try_into().unwrap_or(u64::MAX)),
+            max_trace_filter_concurrency: Some(config.max_trace_filter_concurrency as u64),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn describes_capabilities() {
+        let config = EthConfig::default()
+            .max_tracing_requests(4)
+            .max_trace_call_many(8)
+            .trace_call_timeout(Duration::from_secs(2));
+        let capabilities = trace_capabilities(["trace_filter", "trace_call"], &config);
+
+        assert_eq!(capabilities.methods, vec!["trace_call", "trace_filter"]);
+        assert_eq!(
+            capabilities.output_formats.contains(&TraceOutputFormat::Js),
+            cfg!(feature = "js-tracer")
+        );
+        assert_eq!(capabilities.overrides.state, vec!["trace_call"]);
+        assert_eq!(capabilities.overrides.block, vec!["trace_call", "trace_rawTransaction"]);
+        assert_eq!(
+            capabilities.limits,
+            TraceLimits {
+                max_trace_filter_blocks: config.max_trace_filter_blocks,
+                max_tracing_requests: 4,
+                rpc_gas_cap: config.rpc_gas_cap,
+                max_traces_per_response: None,
+                max_trace_call_many: Some(8),
+                trace_call_timeout: Some(2_000),
+                max_trace_filter_concurrency: Some(config.max_trace_filter_concurrency as u64),
+            }
+        );
+
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["outputFormats"][1], "flatCall");
+        assert_eq!(json["limits"]["maxTracingRequests"], "0x4");
+        assert_eq!(json["limits"]["traceCallTimeout"], "0x7d0");
+        assert!(json["limits"].get("maxTracesPerResponse").is_none());
+    }
+}
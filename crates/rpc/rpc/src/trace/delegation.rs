@@ -0,0 +1,114 @@
+//! Resolution of EIP-7702 delegations of the calls of a `trace_call`.
+
+use alloy_primitives::{map::HashMap, Address};
+use alloy_rpc_types_trace::parity::{Action, TransactionTrace};
+use revm::{bytecode::Bytecode, state::EvmState, DatabaseRef};
+use serde::{Deserialize, Serialize};
+
+/// A call into an account that delegates to the code of another account via EIP-7702.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallDelegation {
+    /// The trace address of the call.
+    pub trace_address: Vec<usize>,
+    /// The callee, i.e. the delegating account.
+    pub address: Address,
+    /// The account whose code was executed.
+    pub delegate: Address,
+}
+
+/// Returns the delegations of all calls of the given traces into accounts with an EIP-7702
+/// delegation designator.
+///
+/// The designators are read from the post-execution `state`, falling back to `db` for accounts
+/// the call didn't load. Authorizations are applied before execution, so the designators are the
+/// ones in effect during the calls.
+pub(super) fn call_delegations<DB: DatabaseRef>(
+    traces: &[TransactionTrace],
+    state: &EvmState,
+    db: DB,
+) -> Result<Vec<CallDelegation>, DB::Error> {
+    let mut delegates: HashMap<Address, Option<Address>> = HashMap::default();
+    let mut delegations = Vec::new();
+    for trace in traces {
+        let Action::Call(call) = &trace.action else { continue };
+        let delegate = match delegates.get(&call.to) {
+            Some(delegate) => *delegate,
+            None => {
+                let delegate = delegate_of(call.to, state, &db)?;
+                delegates.insert(call.to, delegate);
+                delegate
+            }
+        };
+        if let Some(delegate) = delegate {
+            delegations.push(CallDelegation {
+                trace_address: trace.trace_address.clone(),
+                address: call.to,
+                delegate,
+            });
+        }
+    }
+    Ok(delegations)
+}
+
+/// Returns the delegate of the given account, if its code is a delegation designator.
+fn delegate_of<DB: DatabaseRef>(
+    address: Address,
+    state: &EvmState,
+    db: DB,
+) -> Result<Option<Address>, DB::Error> {
+    let info = match state.get(&address) {
+        Some(account) => account.info.clone(),
+        None => db.basic_ref(address)?.unwrap_or_default(),
+    };
+    let code = match info.code {
+        Some(code) => code,
+        None => db.code_by_hash_ref(info.code_hash)?,
+    };
+    Ok(match code {
+        Bytecode::Eip7702(code) => Some(code.address()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::trace;
+    use alloy_primitives::{address, bytes};
+    use alloy_rpc_types_trace::parity::CallAction;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::state::AccountInfo;
+
+    #[test]
+    fn resolves_call_delegations() {
+        let eoa = address!("0x00000000000000000000000000000000000000aa");
+        let delegate = address!("0x00000000000000000000000000000000000000dd");
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            eoa,
+            AccountInfo { code: Some(Bytecode::new_eip7702(delegate)), ..Default::default() },
+        );
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(bytes!("00"))), ..Default::default() },
+        );
+
+        let traces = vec![
+            trace(Action::Call(CallAction { to: contract, ..Default::default() }), vec![]),
+            trace(Action::Call(CallAction { to: eoa, ..Default::default() }), vec![0]),
+            trace(Action::Call(CallAction { to: contract, ..Default::default() }), vec![1]),
+            trace(Action::Call(CallAction { to: eoa, ..Default::default() }), vec![2]),
+        ];
+        let delegations = call_delegations(&traces, &EvmState::default(), &db).unwrap();
+
+        assert_eq!(
+            delegations,
+            vec![
+                CallDelegation { trace_address: vec![0], address: eoa, delegate },
+                CallDelegation { trace_address: vec![2], address: eoa, delegate },
+            ]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Structural diff of two traces of the same transaction.
+
+use alloy_primitives::map::HashMap;
+use alloy_rpc_types_trace::parity::{LocalizedTransactionTrace, TransactionTrace};
+use serde::{Deserialize, Serialize};
+
+/// The difference between two traces of the same transaction, e.g. the transaction traced on top
+/// of two different states or by two different clients.
+///
+/// Frames of both traces are aligned by their trace address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceDiff {
+    /// Frames that are only present in the first trace.
+    pub only_in_a: Vec<LocalizedTransactionTrace>,
+    /// Frames that are only present in the second trace.
+    pub only_in_b: Vec<LocalizedTransactionTrace>,
+    /// Frames that are present in both traces, but differ in gas used, output or error.
+    pub changed: Vec<TraceFrameDiff>,
+}
+
+impl TraceDiff {
+    /// Computes the difference between the two given traces.
+    ///
+    /// Frames present in both traces are listed in the order of the first trace, frames that are
+    /// only present in one of the traces are listed in the order of that trace.
+    pub fn new(a: &[LocalizedTransactionTrace], b: &[LocalizedTransactionTrace]) -> Self {
+        let frames_a = frames_by_address(a);
+        let frames_b = frames_by_address(b);
+
+        let mut diff = Self::default();
+        for frame in a {
+            match frames_b.get(frame.trace.trace_address.as_slice()) {
+                Some(other) => {
+                    if !has_same_outcome(&frame.trace, &other.trace) {
+                        diff.changed.push(TraceFrameDiff {
+                            trace_address: frame.trace.trace_address.clone(),
+                            a: frame.clone(),
+                            b: (*other).clone(),
+                        });
+                    }
+                }
+                None => diff.only_in_a.push(frame.clone()),
+            }
+        }
+        diff.only_in_b.extend(
+            b.iter()
+                .filter(|frame| !frames_a.contains_key(frame.trace.trace_address.as_slice()))
+                .cloned(),
+        );
+        diff
+    }
+
+    /// Returns `true` if both traces are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A frame that is present in both traces of a [`TraceDiff`] but differs in gas used, output or
+/// error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFrameDiff {
+    /// The trace address of the frame.
+    pub trace_address: Vec<usize>,
+    /// The frame of the first trace.
+    pub a: LocalizedTransactionTrace,
+    /// The frame of the second trace.
+    pub b: LocalizedTransactionTrace,
+}
+
+fn frames_by_address(
+    traces: &[LocalizedTransactionTrace],
+) -> HashMap<&[usize], &LocalizedTransactionTrace> {
+    traces.iter().map(|frame| (frame.trace.trace_address.as_slice(), frame)).collect()
+}
+
+/// Returns `true` if both frames used the same amount of gas, returned the same output and failed
+/// with the same error.
+fn has_same_outcome(a: &TransactionTrace, b: &TransactionTrace) -> bool {
+    let gas_used = |trace: &TransactionTrace| trace.result.as_ref().map(|res| res.gas_used());
+    let output = |trace: &TransactionTrace| trace.result.as_ref().map(|res| res.output().clone());
+    gas_used(a) == gas_used(b) && output(a) == output(b) && a.error == b.error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::{call_trace, localized};
+    use alloy_primitives::{address, bytes, Bytes, TxKind, U256};
+    use alloy_rpc_types_eth::TransactionInfo;
+    use alloy_rpc_types_trace::parity::{CallOutput, TraceOutput};
+    use reth_evm::{ConfigureEvm, Evm, EvmEnv};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::{bytecode::Bytecode, context::TxEnv, state::AccountInfo};
+    use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+
+    fn frame(trace_address: Vec<usize>, gas_used: u64, output: Bytes) -> LocalizedTransactionTrace {
+        localized(TransactionTrace {
+            result: Some(TraceOutput::Call(CallOutput { gas_used, output })),
+            ..call_trace(trace_address)
+        })
+    }
+
+    #[test]
+    fn diffs_traces_of_different_states() {
+        // the same transaction traced on top of two different states: with the second state the
+        // first subcall reverts and the second subcall is never made
+        let a = vec![
+            frame(vec![], 50_000, bytes!("01")),
+            frame(vec![0], 20_000, bytes!("01")),
+            frame(vec![1], 5_000, Bytes::new()),
+        ];
+        let mut reverted = frame(vec![0], 12_000, bytes!("08c379a0"));
+        reverted.trace.error = Some("Reverted".to_string());
+        let b = vec![frame(vec![], 50_000, bytes!("01")), reverted.clone()];
+
+        assert!(TraceDiff::new(&a, &a).is_empty());
+
+        let diff = TraceDiff::new(&a, &b);
+        assert_eq!(diff.only_in_a, vec![a[2].clone()]);
+        assert!(diff.only_in_b.is_empty());
+        assert_eq!(
+            diff.changed,
+            vec![TraceFrameDiff { trace_address: vec![0], a: a[1].clone(), b: reverted }]
+        );
+
+        let diff = TraceDiff::new(&b, &a);
+        assert!(diff.only_in_a.is_empty());
+        assert_eq!(diff.only_in_b, vec![a[2].clone()]);
+    }
+
+    #[test]
+    fn diffs_executions_on_different_storage() {
+        let outer = address!("0x00000000000000000000000000000000000000cc");
+        let inner = address!("0x00000000000000000000000000000000000000bb");
+        // calls the inner contract
+        let outer_code =
+            bytes!("600060006000600060007300000000000000000000000000000000000000bb5af15000");
+        // reverts if slot 0 is set
+        let inner_code = bytes!("600054600757005b60006000fd");
+        let traces = |slot: U256| {
+            let mut db = CacheDB::new(EmptyDB::default());
+            db.insert_account_info(
+                outer,
+                AccountInfo {
+                    code: Some(Bytecode::new_raw(outer_code.clone())),
+                    ..Default::default()
+                },
+            );
+            db.insert_account_info(
+                inner,
+                AccountInfo {
+                    code: Some(Bytecode::new_raw(inner_code.clone())),
+                    ..Default::default()
+                },
+            );
+            db.insert_account_storage(inner, U256::ZERO, slot).unwrap();
+            let tx_env =
+                TxEnv { kind: TxKind::Call(outer), gas_limit: 100_000, ..Default::default() };
+
+            let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+            let res = EthEvmConfig::mainnet()
+                .evm_with_env_and_inspector(db, EvmEnv::default(), &mut inspector)
+                .transact(tx_env)
+                .unwrap();
+            assert!(res.result.is_success());
+            inspector
+                .into_parity_builder()
+                .into_localized_transaction_traces(TransactionInfo::default())
+        };
+
+        let a = traces(U256::ZERO);
+        let b = traces(U256::from(1));
+        assert_eq!(a.len(), 2);
+        assert!(TraceDiff::new(&a, &traces(U256::ZERO)).is_empty());
+
+        // the inner call reverts on top of the second state, which also changes the gas used by
+        // the outer call
+        let diff = TraceDiff::new(&a, &b);
+        assert!(diff.only_in_a.is_empty());
+        assert!(diff.only_in_b.is_empty());
+        assert_eq!(
+            diff.changed.iter().map(|frame| frame.trace_address.clone()).collect::<Vec<_>>(),
+            vec![vec![], vec![0]]
+        );
+        assert_eq!(diff.changed[1].a.trace.error, None);
+        assert_eq!(diff.changed[1].b.trace.error.as_deref(), Some("Reverted"));
+    }
+}
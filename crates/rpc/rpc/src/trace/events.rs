@@ -0,0 +1,150 @@
+//! Recognition of well-known token events in the logs emitted by a traced call.
+
+use alloy_primitives::{b256, Address, Log, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// `Transfer(address,address,uint256)`, shared by ERC-20 and ERC-721.
+const TRANSFER_EVENT: B256 =
+    b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// `Approval(address,address,uint256)`, shared by ERC-20 and ERC-721.
+const APPROVAL_EVENT: B256 =
+    b256!("0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+
+/// A log emitted by a traced call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallLog {
+    /// The raw log.
+    #[serde(flatten)]
+    pub log: Log,
+    /// The decoded event, if decoding was requested and the log is a well-known event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedEvent>,
+}
+
+/// A well-known token event with a fixed signature.
+///
+/// ERC-20 and ERC-721 share the `Transfer` signature, they are told apart by the number of indexed
+/// topics: the ERC-20 amount is part of the data, the ERC-721 token id is indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DecodedEvent {
+    /// An ERC-20 `Transfer` of `value` tokens.
+    Erc20Transfer {
+        /// The sender of the tokens.
+        from: Address,
+        /// The recipient of the tokens.
+        to: Address,
+        /// The amount of tokens.
+        value: U256,
+    },
+    /// An ERC-20 `Approval` of `value` tokens.
+    Erc20Approval {
+        /// The owner of the tokens.
+        owner: Address,
+        /// The account allowed to spend the tokens.
+        spender: Address,
+        /// The approved amount of tokens.
+        value: U256,
+    },
+    /// An ERC-721 `Transfer` of the token with id `token_id`.
+    Erc721Transfer {
+        /// The previous owner of the token.
+        from: Address,
+        /// The new owner of the token.
+        to: Address,
+        /// The id of the token.
+        token_id: U256,
+    },
+}
+
+impl DecodedEvent {
+    /// Decodes the given log if it is one of the recognized events.
+    pub fn decode(log: &Log) -> Option<Self> {
+        let topics = log.topics();
+        let data = log.data.data.as_ref();
+        let address = |topic: &B256| Address::from_word(*topic);
+        match (topics, data.len()) {
+            ([sig, from, to], 32) if *sig == TRANSFER_EVENT => Some(Self::Erc20Transfer {
+                from: address(from),
+                to: address(to),
+                value: U256::from_be_slice(data),
+            }),
+            ([sig, owner, spender], 32) if *sig == APPROVAL_EVENT => Some(Self::Erc20Approval {
+                owner: address(owner),
+                spender: address(spender),
+                value: U256::from_be_slice(data),
+            }),
+            ([sig, from, to, token_id], 0) if *sig == TRANSFER_EVENT => {
+                Some(Self::Erc721Transfer {
+                    from: address(from),
+                    to: address(to),
+                    token_id: (*token_id).into(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the given logs, with the recognized events decoded if `decode_events` is set.
+pub(super) fn trace_call_logs(logs: &[Log], decode_events: bool) -> Vec<TraceCallLog> {
+    logs.iter()
+        .map(|log| TraceCallLog {
+            log: log.clone(),
+            decoded: decode_events.then(|| DecodedEvent::decode(log)).flatten(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256, Bytes};
+
+    fn log(topics: Vec<B256>, data: Bytes) -> Log {
+        Log::new_unchecked(address!("0x00000000000000000000000000000000000000cc"), topics, data)
+    }
+
+    #[test]
+    fn decodes_token_events() {
+        assert_eq!(TRANSFER_EVENT, keccak256("Transfer(address,address,uint256)"));
+        assert_eq!(APPROVAL_EVENT, keccak256("Approval(address,address,uint256)"));
+
+        let from = address!("0x00000000000000000000000000000000000000aa");
+        let to = address!("0x00000000000000000000000000000000000000bb");
+        let amount = U256::from(100_000_000u64);
+
+        let erc20 = log(
+            vec![TRANSFER_EVENT, from.into_word(), to.into_word()],
+            amount.to_be_bytes_vec().into(),
+        );
+        let approval = log(
+            vec![APPROVAL_EVENT, from.into_word(), to.into_word()],
+            amount.to_be_bytes_vec().into(),
+        );
+        let erc721 = log(
+            vec![TRANSFER_EVENT, from.into_word(), to.into_word(), U256::from(7).into()],
+            Bytes::new(),
+        );
+        let unknown = log(vec![keccak256("Deposit(address,uint256)")], Bytes::new());
+
+        let logs = trace_call_logs(&[erc20, approval, erc721, unknown.clone()], true);
+        let decoded = logs.iter().map(|log| log.decoded).collect::<Vec<_>>();
+        assert_eq!(
+            decoded,
+            vec![
+                Some(DecodedEvent::Erc20Transfer { from, to, value: amount }),
+                Some(DecodedEvent::Erc20Approval { owner: from, spender: to, value: amount }),
+                Some(DecodedEvent::Erc721Transfer { from, to, token_id: U256::from(7) }),
+                None,
+            ]
+        );
+        // the raw log is always kept
+        assert_eq!(logs[3].log, unknown);
+
+        let raw = trace_call_logs(&[unknown], false);
+        assert_eq!(raw[0].decoded, None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Attribution of the logs of a transaction to the call frames emitting them.
+
+use alloy_primitives::{map::HashMap, Address, Log, U256};
+use alloy_rpc_types_eth::TransactionInfo;
+use alloy_rpc_types_trace::parity::LocalizedTransactionTrace;
+use revm::{
+    context_interface::ContextTr,
+    inspector::JournalExt,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Inspector,
+};
+use revm_inspectors::tracing::TracingInspector;
+use serde::{Deserialize, Serialize};
+
+/// A trace of a transaction, along with the logs emitted by its frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTraceWithLogs {
+    /// The trace of the frame.
+    #[serde(flatten)]
+    pub trace: LocalizedTransactionTrace,
+    /// The logs emitted by the frame itself, in emission order, excluding the logs of its
+    /// subcalls.
+    ///
+    /// This includes the logs of frames that reverted, which aren't part of the receipt.
+    pub logs: Vec<Log>,
+}
+
+/// An inspector that collects the parity traces like the [`TracingInspector`] it wraps, and
+/// records the logs emitted by each frame, keyed by the trace address of the frame.
+#[derive(Debug)]
+pub(super) struct FrameLogsInspector {
+    /// Collects the call traces.
+    tracing: TracingInspector,
+    /// The trace address of each active frame, along with the number of its subcalls so far.
+    frames: Vec<(Vec<usize>, usize)>,
+    /// The logs emitted by each frame.
+    logs: HashMap<Vec<usize>, Vec<Log>>,
+}
+
+impl FrameLogsInspector {
+    /// Creates a new inspector that collects the traces with the given [`TracingInspector`].
+    pub(super) fn new(tracing: TracingInspector) -> Self {
+        Self { tracing, frames: Vec::new(), logs: HashMap::default() }
+    }
+
+    /// Consumes the inspector and returns the localized traces of the transaction with the logs
+    /// of their frames.
+    pub(super) fn into_traces_with_logs(
+        self,
+        tx_info: TransactionInfo,
+    ) -> Vec<TransactionTraceWithLogs> {
+        let mut logs = self.logs;
+        self.tracing
+            .into_parity_builder()
+            .into_localized_transaction_traces(tx_info)
+            .into_iter()
+            .map(|trace| TransactionTraceWithLogs {
+                logs: logs.remove(&trace.trace.trace_address).unwrap_or_default(),
+                trace,
+            })
+            .collect()
+    }
+
+    /// Enters a new call or create frame, which is the next subcall of the current frame.
+    fn enter_frame(&mut self) {
+        let trace_address = match self.frames.last_mut() {
+            Some((parent, subcalls)) => {
+                let mut trace_address = parent.clone();
+                trace_address.push(*subcalls);
+                *subcalls += 1;
+                trace_address
+            }
+            None => Vec::new(),
+        };
+        self.frames.push((trace_address, 0));
+    }
+}
+
+impl<CTX> Inspector<CTX> for FrameLogsInspector
+where
+    CTX: ContextTr<Journal: JournalExt>,
+{
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        self.tracing.initialize_interp(interp, context);
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        self.tracing.step(interp, context);
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        self.tracing.step_end(interp, context);
+    }
+
+    fn log(&mut self, interp: &mut Interpreter, context: &mut CTX, log: Log) {
+        if let Some((trace_address, _)) = self.frames.last() {
+            self.logs.entry(trace_address.clone()).or_default().push(log.clone());
+        }
+        self.tracing.log(interp, context, log);
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.enter_frame();
+        self.tracing.call(context, inputs)
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.tracing.call_end(context, inputs, outcome);
+        self.frames.pop();
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.enter_frame();
+        self.tracing.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.tracing.create_end(context, inputs, outcome);
+        self.frames.pop();
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        Inspector::<CTX>::selfdestruct(&mut self.tracing, contract, target, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes, Bytes, TxKind, B256};
+    use reth_evm::{ConfigureEvm, Evm, EvmEnv};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::{bytecode::Bytecode, context::TxEnv, state::AccountInfo};
+    use revm_inspectors::tracing::TracingInspectorConfig;
+
+    #[test]
+    fn attributes_logs_to_frames() {
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let outer = address!("0x00000000000000000000000000000000000000cc");
+        let inner = address!("0x00000000000000000000000000000000000000bb");
+        // emits a LOG0, calls the inner contract and emits a LOG1 with topic 2
+        let outer_code = bytes!(
+            "60006000a0600060006000600060007300000000000000000000000000000000000000bb5af150600260006000a100"
+        );
+        // emits a LOG1 with topic 1
+        let inner_code = bytes!("600160006000a100");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            outer,
+            AccountInfo { code: Some(Bytecode::new_raw(outer_code)), ..Default::default() },
+        );
+        db.insert_account_info(
+            inner,
+            AccountInfo { code: Some(Bytecode::new_raw(inner_code)), ..Default::default() },
+        );
+        let tx_env =
+            TxEnv { caller, kind: TxKind::Call(outer), gas_limit: 100_000, ..Default::default() };
+
+        let mut inspector = FrameLogsInspector::new(TracingInspector::new(
+            TracingInspectorConfig::default_parity(),
+        ));
+        let res = EthEvmConfig::mainnet()
+            .evm_with_env_and_inspector(db, EvmEnv::default(), &mut inspector)
+            .transact(tx_env)
+            .unwrap();
+        assert!(res.result.is_success());
+        let traces = inspector.into_traces_with_logs(TransactionInfo::default());
+
+        let log = |address, topics: Vec<B256>| Log::new_unchecked(address, topics, Bytes::new());
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace.trace.trace_address, Vec::<usize>::new());
+        assert_eq!(
+            traces[0].logs,
+            vec![log(outer, vec![]), log(outer, vec![B256::with_last_byte(2)])]
+        );
+        assert_eq!(traces[1].trace.trace.trace_address, vec![0]);
+        assert_eq!(traces[1].logs, vec![log(inner, vec![B256::with_last_byte(1)])]);
+    }
+}
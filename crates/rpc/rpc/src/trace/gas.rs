@@ -0,0 +1,223 @@
+//! Split of the gas used by a traced call into intrinsic and execution gas.
+
+use reth_chainspec::{EthereumHardfork, EthereumHardforks};
+use reth_rpc_eth_types::error::{api::FromEvmHalt, AsEthApiError};
+use revm::{
+    context::result::ExecutionResult, context_interface::Transaction,
+    interpreter::gas::calculate_initial_tx_gas_for_tx, primitives::hardfork::SpecId,
+};
+use revm_inspectors::tracing::TracingInspector;
+use serde::{Deserialize, Serialize};
+
+/// The gas of a call, split into the intrinsic gas that is charged before execution and the gas
+/// used by the execution itself.
+///
+/// The gas used by the call is `intrinsic_gas + execution_gas - refund`, unless the EIP-7623
+/// calldata floor is higher, in which case the floor is charged and nothing is refunded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasSplit {
+    /// The base cost of the call plus the cost of its calldata, access list and authorization
+    /// list.
+    #[serde(with = "alloy_serde::quantity")]
+    pub intrinsic_gas: u64,
+    /// The gas used by the execution of the call, before refunds.
+    #[serde(with = "alloy_serde::quantity")]
+    pub execution_gas: u64,
+    /// The gas refunded after execution.
+    #[serde(with = "alloy_serde::quantity")]
+    pub refund: u64,
+}
+
+impl GasSplit {
+    /// Creates the split from the intrinsic gas of a call and the outcome of its execution.
+    ///
+    /// The execution gas is the gas used by the top-level frame recorded by the inspector.
+    pub(super) fn new<H>(
+        intrinsic_gas: u64,
+        inspector: &TracingInspector,
+        result: &ExecutionResult<H>,
+    ) -> Self {
+        let execution_gas =
+            inspector.traces().nodes().first().map(|node| node.trace.gas_used).unwrap_or_default();
+        let refund = match result {
+            ExecutionResult::Success { gas_refunded, .. } => *gas_refunded,
+            _ => 0,
+        };
+        Self { intrinsic_gas, execution_gas, refund }
+    }
+}
+
+/// The gas used by a call compared to its gas limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasInfo {
+    /// The gas limit of the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// The gas used by the call, including the intrinsic gas and after refunds.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Whether the call ran out of gas, i.e. the gas limit wasn't sufficient.
+    pub out_of_gas: bool,
+}
+
+impl GasInfo {
+    /// Creates the gas info from the gas limit of a call and the outcome of its execution.
+    ///
+    /// Whether a halt is caused by running out of gas is determined by the error `E` the halt
+    /// converts into.
+    pub(super) fn new<E, H>(gas_limit: u64, result: &ExecutionResult<H>) -> Self
+    where
+        E: FromEvmHalt<H> + AsEthApiError,
+        H: Clone,
+    {
+        let out_of_gas = match result {
+            ExecutionResult::Halt { reason, .. } => E::from_evm_halt(reason.clone(), gas_limit)
+                .as_err()
+                .is_some_and(|err| err.is_out_of_gas()),
+            _ => false,
+        };
+        Self { gas_limit, gas_used: result.gas_used(), out_of_gas }
+    }
+}
+
+/// Returns the intrinsic gas of the given transaction at the given block.
+pub(super) fn intrinsic_gas(
+    chain_spec: &impl EthereumHardforks,
+    block_number: u64,
+    timestamp: u64,
+    tx: impl Transaction,
+) -> u64 {
+    calculate_initial_tx_gas_for_tx(tx, intrinsic_gas_spec(chain_spec, block_number, timestamp))
+        .initial_gas
+}
+
+/// Returns the latest [`SpecId`] at the given block that changed the intrinsic gas.
+fn intrinsic_gas_spec(
+    chain_spec: &impl EthereumHardforks,
+    block_number: u64,
+    timestamp: u64,
+) -> SpecId {
+    if chain_spec.is_prague_active_at_timestamp(timestamp) {
+        SpecId::PRAGUE
+    } else if chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+        SpecId::SHANGHAI
+    } else if chain_spec.is_ethereum_fork_active_at_block(EthereumHardfork::Istanbul, block_number)
+    {
+        SpecId::ISTANBUL
+    } else if chain_spec.is_homestead_active_at_block(block_number) {
+        SpecId::HOMESTEAD
+    } else {
+        SpecId::FRONTIER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::{address, bytes, Address, Bytes, TxKind, B256};
+    use reth_chainspec::MAINNET;
+    use reth_evm::{ConfigureEvm, Evm, EvmEnv};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use reth_rpc_eth_types::EthApiError;
+    use revm::{bytecode::Bytecode, context::TxEnv, state::AccountInfo};
+    use revm_inspectors::tracing::TracingInspectorConfig;
+
+    fn gas_split(tx: TxEnv) -> (GasSplit, u64) {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        // sets slot 0 to 1 and resets it to 0
+        let code = bytes!("60016000556000600055");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        // the default env is prague
+        let evm_env = EvmEnv::default();
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+        let intrinsic = intrinsic_gas(&*MAINNET, 0, u64::MAX, tx.clone());
+        let mut evm =
+            EthEvmConfig::mainnet().evm_with_env_and_inspector(db, evm_env, &mut inspector);
+        let result = evm.transact(tx).unwrap().result;
+        drop(evm);
+        (GasSplit::new(intrinsic, &inspector, &result), result.gas_used())
+    }
+
+    #[test]
+    fn splits_intrinsic_and_execution_gas() {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        let tx = |tx_type, kind, data: Bytes, access_list: AccessList| TxEnv {
+            tx_type,
+            kind,
+            data,
+            access_list,
+            gas_limit: 100_000,
+            ..Default::default()
+        };
+
+        // legacy call without code: 1 zero and 3 non-zero bytes of calldata, the calldata floor
+        // exceeds the intrinsic gas
+        let (split, gas_used) =
+            gas_split(tx(0, TxKind::Call(Address::ZERO), bytes!("00010203"), Default::default()));
+        assert_eq!(
+            split,
+            GasSplit { intrinsic_gas: 21_000 + 4 + 3 * 16, execution_gas: 0, refund: 0 }
+        );
+        assert_eq!(gas_used, 21_000 + 10 * (1 + 3 * 4));
+
+        // access list call: the slot is warm, the reset refunds 19900 capped at a fifth of the
+        // gas spent
+        let access_list =
+            AccessList(vec![AccessListItem { address: contract, storage_keys: vec![B256::ZERO] }]);
+        let (split, gas_used) = gas_split(tx(1, TxKind::Call(contract), Bytes::new(), access_list));
+        let intrinsic_gas = 21_000 + 2_400 + 1_900;
+        let execution_gas = 4 * 3 + 20_000 + 100;
+        let refund = (intrinsic_gas + execution_gas) / 5;
+        assert_eq!(split, GasSplit { intrinsic_gas, execution_gas, refund });
+        assert_eq!(gas_used, intrinsic_gas + execution_gas - refund);
+
+        // create with a single STOP as initcode
+        let (split, gas_used) = gas_split(tx(0, TxKind::Create, bytes!("00"), Default::default()));
+        assert_eq!(split, GasSplit { intrinsic_gas: 53_000 + 4 + 2, execution_gas: 0, refund: 0 });
+        assert_eq!(gas_used, 53_006);
+    }
+
+    #[test]
+    fn reports_out_of_gas() {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        let gas_info = |gas_limit| {
+            // sets slot 0 to 1
+            let code = bytes!("6001600055");
+            let mut db = CacheDB::new(EmptyDB::default());
+            db.insert_account_info(
+                contract,
+                AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+            );
+            let tx = TxEnv { kind: TxKind::Call(contract), gas_limit, ..Default::default() };
+            let result =
+                EthEvmConfig::mainnet().evm_with_env(db, EvmEnv::default()).transact(tx).unwrap();
+            GasInfo::new::<EthApiError, _>(gas_limit, &result.result)
+        };
+
+        let sufficient = gas_info(100_000);
+        assert!(!sufficient.out_of_gas);
+        assert_eq!(sufficient.gas_used, 21_000 + 2 * 3 + 22_100);
+
+        // the SSTORE needs more than the gas left after the intrinsic gas
+        let insufficient = gas_info(22_000);
+        assert_eq!(insufficient, GasInfo { gas_limit: 22_000, gas_used: 22_000, out_of_gas: true });
+    }
+
+    #[test]
+    fn selects_intrinsic_gas_spec() {
+        assert_eq!(intrinsic_gas_spec(&*MAINNET, 0, 0), SpecId::FRONTIER);
+        assert_eq!(intrinsic_gas_spec(&*MAINNET, 1_150_000, 0), SpecId::HOMESTEAD);
+        assert_eq!(intrinsic_gas_spec(&*MAINNET, 9_069_000, 0), SpecId::ISTANBUL);
+        assert_eq!(intrinsic_gas_spec(&*MAINNET, 17_034_870, 1681338455), SpecId::SHANGHAI);
+        assert_eq!(intrinsic_gas_spec(&*MAINNET, 22_431_084, 1746612311), SpecId::PRAGUE);
+    }
+}
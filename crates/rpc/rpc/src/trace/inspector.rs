@@ -0,0 +1,293 @@
+//! Inspector used by `trace_call` to collect additional diagnostics in the same pass.
+
+use alloy_primitives::{map::HashMap, Address, Log, U256};
+use core::fmt;
+use revm::{
+    bytecode::opcode::OpCode,
+    context_interface::ContextTr,
+    inspector::JournalExt,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Inspector,
+};
+use revm_inspectors::{opcode::OpcodeGasInspector, tracing::TracingInspector};
+use std::sync::Arc;
+
+/// A custom inspector that can be registered on the [`TraceApi`](super::TraceApi) to run alongside
+/// the parity tracer in
+/// [`TraceApi::trace_call_with_options`](super::TraceApi::trace_call_with_options).
+///
+/// Unlike a revm [`Inspector`], the hooks are independent of the EVM context and can't alter the
+/// execution. All hooks are no-ops by default, the result of the inspector is returned as JSON.
+///
+/// # Example
+///
+/// ```
+/// use reth_rpc::{CustomTraceInspector, TraceApi};
+/// use revm::interpreter::{CallInputs, CallOutcome};
+/// use std::sync::Arc;
+///
+/// /// Counts the number of calls made.
+/// #[derive(Default)]
+/// struct CallCounter(u64);
+///
+/// impl CustomTraceInspector for CallCounter {
+///     fn call_end(&mut self, _inputs: &CallInputs, _outcome: &CallOutcome) {
+///         self.0 += 1;
+///     }
+///
+///     fn into_json(self: Box<Self>) -> serde_json::Value {
+///         self.0.into()
+///     }
+/// }
+///
+/// fn register<Eth>(trace_api: &TraceApi<Eth>) {
+///     trace_api.register_custom_inspector(Arc::new(|| Box::new(CallCounter::default())));
+/// }
+/// ```
+pub trait CustomTraceInspector: Send {
+    /// Called on each step of the interpreter.
+    fn step(&mut self, interp: &Interpreter) {
+        let _ = interp;
+    }
+
+    /// Called after the instruction of a step has been executed.
+    fn step_end(&mut self, interp: &Interpreter) {
+        let _ = interp;
+    }
+
+    /// Called when a log is emitted.
+    fn log(&mut self, log: &Log) {
+        let _ = log;
+    }
+
+    /// Called when a call is about to start.
+    fn call(&mut self, inputs: &CallInputs) {
+        let _ = inputs;
+    }
+
+    /// Called when a call has concluded.
+    fn call_end(&mut self, inputs: &CallInputs, outcome: &CallOutcome) {
+        let _ = inputs;
+        let _ = outcome;
+    }
+
+    /// Called when a contract is about to be created.
+    fn create(&mut self, inputs: &CreateInputs) {
+        let _ = inputs;
+    }
+
+    /// Called when a contract has been created.
+    fn create_end(&mut self, inputs: &CreateInputs, outcome: &CreateOutcome) {
+        let _ = inputs;
+        let _ = outcome;
+    }
+
+    /// Called when a contract has been self-destructed.
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        let _ = contract;
+        let _ = target;
+        let _ = value;
+    }
+
+    /// Consumes the inspector and returns its result.
+    fn into_json(self: Box<Self>) -> serde_json::Value;
+}
+
+/// Creates a new [`CustomTraceInspector`] for each traced call.
+pub type CustomTraceInspectorFactory = Arc<dyn Fn() -> Box<dyn CustomTraceInspector> + Send + Sync>;
+
+/// Combines the [`TracingInspector`] with the optional inspectors requested via
+/// [`TraceCallOptions`](super::TraceCallOptions), so that a call only needs to be executed once.
+pub(super) struct TraceCallInspector {
+    /// Collects the call traces.
+    pub(super) tracing: TracingInspector,
+    /// Counts the executed opcodes, if requested.
+    pub(super) opcodes: Option<OpcodeGasInspector>,
+    /// The registered custom inspector, if requested.
+    pub(super) custom: Option<Box<dyn CustomTraceInspector>>,
+}
+
+impl fmt::Debug for TraceCallInspector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceCallInspector")
+            .field("tracing", &self.tracing)
+            .field("opcodes", &self.opcodes)
+            .field("custom", &self.custom.is_some())
+            .finish()
+    }
+}
+
+impl TraceCallInspector {
+    /// Returns the number of times each opcode was executed, if requested.
+    pub(super) fn opcode_counts(&self) -> Option<HashMap<OpCode, u64>> {
+        self.opcodes.as_ref().map(|opcodes| opcodes.opcode_counts().clone())
+    }
+}
+
+impl<CTX> Inspector<CTX> for TraceCallInspector
+where
+    CTX: ContextTr<Journal: JournalExt>,
+{
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        self.tracing.initialize_interp(interp, context);
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.initialize_interp(interp, context);
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        self.tracing.step(interp, context);
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.step(interp, context);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.step(interp);
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        self.tracing.step_end(interp, context);
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.step_end(interp, context);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.step_end(interp);
+        }
+    }
+
+    fn log(&mut self, interp: &mut Interpreter, context: &mut CTX, log: Log) {
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.log(interp, context, log.clone());
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.log(&log);
+        }
+        self.tracing.log(interp, context, log);
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.call(context, inputs);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.call(inputs);
+        }
+        self.tracing.call(context, inputs)
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.tracing.call_end(context, inputs, outcome);
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.call_end(context, inputs, outcome);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.call_end(inputs, outcome);
+        }
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.create(context, inputs);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.create(inputs);
+        }
+        self.tracing.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.tracing.create_end(context, inputs, outcome);
+        if let Some(opcodes) = &mut self.opcodes {
+            opcodes.create_end(context, inputs, outcome);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.create_end(inputs, outcome);
+        }
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        Inspector::<CTX>::selfdestruct(&mut self.tracing, contract, target, value);
+        if let Some(opcodes) = &mut self.opcodes {
+            Inspector::<CTX>::selfdestruct(opcodes, contract, target, value);
+        }
+        if let Some(custom) = &mut self.custom {
+            custom.selfdestruct(contract, target, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes, TxKind};
+    use reth_evm::{ConfigureEvm, Evm, EvmEnv, InspectorFor};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::{bytecode::Bytecode, context::TxEnv, state::AccountInfo};
+    use revm_inspectors::tracing::TracingInspectorConfig;
+
+    fn transact<I: InspectorFor<EthEvmConfig, CacheDB<EmptyDB>>>(inspector: I) {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        // stores 1 + 2 at memory offset 0 and returns it
+        let code = bytes!("600160020160005260206000f3");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        let mut evm =
+            EthEvmConfig::mainnet().evm_with_env_and_inspector(db, EvmEnv::default(), inspector);
+        let tx = TxEnv { kind: TxKind::Call(contract), gas_limit: 100_000, ..Default::default() };
+        assert!(evm.transact(tx).unwrap().result.is_success());
+    }
+
+    #[test]
+    fn counts_opcodes_like_standalone_inspector() {
+        let mut standalone = OpcodeGasInspector::default();
+        transact(&mut standalone);
+
+        let mut combined = TraceCallInspector {
+            tracing: TracingInspector::new(TracingInspectorConfig::default_parity()),
+            opcodes: Some(OpcodeGasInspector::default()),
+            custom: None,
+        };
+        transact(&mut combined);
+
+        let counts = combined.opcode_counts().unwrap();
+        assert_eq!(&counts, standalone.opcode_counts());
+        assert_eq!(counts.get(&OpCode::PUSH1), Some(&5));
+        assert_eq!(counts.values().sum::<u64>(), 8);
+    }
+
+    #[test]
+    fn runs_custom_inspector() {
+        #[derive(Default)]
+        struct CallCounter(u64);
+
+        impl CustomTraceInspector for CallCounter {
+            fn call_end(&mut self, _inputs: &CallInputs, _outcome: &CallOutcome) {
+                self.0 += 1;
+            }
+
+            fn into_json(self: Box<Self>) -> serde_json::Value {
+                self.0.into()
+            }
+        }
+
+        let factory: CustomTraceInspectorFactory = Arc::new(|| Box::new(CallCounter::default()));
+        let mut combined = TraceCallInspector {
+            tracing: TracingInspector::new(TracingInspectorConfig::default_parity()),
+            opcodes: None,
+            custom: Some(factory()),
+        };
+        transact(&mut combined);
+
+        assert_eq!(combined.custom.unwrap().into_json(), serde_json::json!(1));
+    }
+}
@@ -0,0 +1,3977 @@
+use alloy_consensus::{BlockHeader as _, Transaction as _, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::{BlockId, BlockNumberOrTag, RpcBlockHash};
+use alloy_evm::{block::calc::base_block_reward_pre_merge, overrides::apply_block_overrides};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address, BlockNumber, Bytes, TxKind, B256, U256, U64,
+};
+use alloy_rpc_types_eth::{
+    state::{EvmOverrides, StateOverride},
+    transaction::TransactionRequest,
+    BlockOverrides, Index, TransactionInfo,
+};
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    geth::{DefaultFrame, GethDefaultTracingOptions},
+    opcode::{BlockOpcodeGas, TransactionOpcodeGas},
+    parity::*,
+    tracerequest::TraceCallRequest,
+};
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use jsonrpsee::core::RpcResult;
+use parking_lot::RwLock;
+use reth_chainspec::{max_code_size, ChainSpecProvider, EthereumHardforks};
+use reth_errors::{ProviderError, RethError};
+use reth_evm::{ConfigureEvm, Evm as _, EvmEnv, EvmEnvFor, TransactionEnv};
+use reth_primitives_traits::{
+    BlockBody, BlockHeader, NodePrimitives, OmmerTy, PooledTxTy, RecoveredBlock, TxTy,
+};
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_rpc_api::{BlockTraces, TraceApiServer, TraceCapabilities};
+use reth_rpc_eth_api::{
+    helpers::{Call, LoadPendingBlock, LoadTransaction, Trace, TraceExt},
+    FromEthApiError, FromEvmError, RpcNodeCore,
+};
+use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
+use reth_storage_api::{
+    canonical_block, BlockBodyIndicesProvider, BlockIdReader, BlockNumReader, BlockReader,
+    BlockReaderIdExt, HeaderProvider, ProviderBlock, ProviderHeader, ReceiptProvider,
+    TraceIndexProvider,
+};
+use reth_tasks::pool::BlockingTaskGuard;
+use revm::{
+    bytecode::{opcode::OpCode, Bytecode},
+    context_interface::{result::ResultAndState, Transaction as _},
+    inspector::NoOpInspector,
+    state::EvmState,
+    DatabaseCommit, DatabaseRef,
+};
+use revm_inspectors::{
+    opcode::OpcodeGasInspector,
+    tracing::{
+        parity::populate_state_diff, ParityTraceBuilder, TracingInspector, TracingInspectorConfig,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    future::Future,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
+
+mod authorization;
+use authorization::{authorization_outcomes, validate_authorization_list};
+pub use authorization::{AuthorizationOutcome, AuthorizationRejection};
+
+mod builder;
+pub use builder::TraceApiBuilder;
+
+mod cache;
+use cache::TransactionTraceCache;
+
+mod capabilities;
+use capabilities::trace_capabilities;
+
+mod delegation;
+use delegation::call_delegations;
+pub use delegation::CallDelegation;
+
+mod diff;
+pub use diff::{TraceDiff, TraceFrameDiff};
+
+mod events;
+use events::trace_call_logs;
+pub use events::{DecodedEvent, TraceCallLog};
+
+mod frame_logs;
+use frame_logs::FrameLogsInspector;
+pub use frame_logs::TransactionTraceWithLogs;
+
+mod gas;
+use gas::intrinsic_gas;
+pub use gas::{GasInfo, GasSplit};
+
+mod inspector;
+use inspector::TraceCallInspector;
+pub use inspector::{CustomTraceInspector, CustomTraceInspectorFactory};
+
+mod opcode_address;
+use opcode_address::OpcodeGasByAddressInspector;
+pub use opcode_address::TransactionOpcodeGasByAddress;
+
+mod opcode_category;
+pub use opcode_category::{OpcodeCategory, OpcodeCategoryGas, TransactionOpcodeGasCategorized};
+
+mod precompile;
+use precompile::precompile_calls;
+pub use precompile::{Precompile, PrecompileCall};
+
+mod receipts;
+pub use receipts::TraceReceiptMismatch;
+use receipts::{verify_against_receipts, TracedOutcome};
+
+mod revert;
+pub use revert::RevertedTrace;
+
+mod reward;
+pub use reward::{BlockRewardProvider, EthereumBlockRewards};
+
+mod snapshot;
+use snapshot::account_snapshots;
+pub use snapshot::AccountSnapshot;
+
+#[cfg(feature = "opentelemetry")]
+mod spans;
+#[cfg(feature = "opentelemetry")]
+pub use spans::trace_to_spans;
+
+mod state_root;
+use state_root::verify_state_root;
+
+mod storage_diff;
+use storage_diff::storage_diff_of;
+pub use storage_diff::StorageDiff;
+
+#[cfg(test)]
+mod test_utils;
+
+mod tree;
+use tree::call_tree;
+pub use tree::CallTreeNode;
+
+/// The maximum number of traces buffered by [`TraceApi::trace_block_stream`].
+pub const TRACE_BLOCK_STREAM_BUFFER: usize = 1024;
+
+/// The maximum number of times a traced block is read while it's replaced by reorgs, see
+/// [`canonical_block`].
+const CANONICAL_BLOCK_READ_ATTEMPTS: usize = 3;
+
+/// A recovered block of the provider `P`, shared between tracing tasks.
+type SharedRecoveredBlock<P> = Arc<RecoveredBlock<ProviderBlock<P>>>;
+
+/// `trace` API implementation.
+///
+/// This type provides the functionality for handling `trace` related requests.
+pub struct TraceApi<Eth> {
+    inner: Arc<TraceApiInner<Eth>>,
+}
+
+// === impl TraceApi ===
+
+impl<Eth> TraceApi<Eth> {
+    /// Create a new instance of the [`TraceApi`]
+    ///
+    /// See also [`Self::builder`].
+    pub fn new(
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        eth_config: EthConfig,
+    ) -> Self {
+        TraceApiBuilder::new(eth_api)
+            .blocking_task_guard(blocking_task_guard)
+            .eth_config(eth_config)
+            .build()
+    }
+
+    /// Convenience fn to obtain a new [`TraceApiBuilder`] instance for the given `eth_api`.
+    pub fn builder(eth_api: Eth) -> TraceApiBuilder<Eth> {
+        TraceApiBuilder::new(eth_api)
+    }
+
+    /// Registers a [`CustomTraceInspector`] that runs alongside the parity tracer if requested via
+    /// [`TraceCallOptions::custom_inspector`].
+    ///
+    /// The factory is invoked once per traced call. This replaces any previously registered
+    /// inspector and applies to all clones of this [`TraceApi`].
+    pub fn register_custom_inspector(&self, factory: CustomTraceInspectorFactory) {
+        *self.inner.custom_inspector.write() = Some(factory);
+    }
+
+    /// Registers a [`TraceIndexProvider`] that is used to narrow down the blocks traced by
+    /// [`Self::trace_filter_with_options`] and [`Self::trace_filter_hashes`] to the ones involving
+    /// the filter's `from` and `to` addresses, e.g. the backing provider if it maintains such an
+    /// index.
+    ///
+    /// Without an index, all blocks in the filter's range are traced. The traces are matched
+    /// against the filter either way. This replaces any previously registered index and applies to
+    /// all clones of this [`TraceApi`].
+    pub fn register_trace_index(&self, trace_index: Arc<dyn TraceIndexProvider>) {
+        *self.inner.trace_index.write() = Some(trace_index);
+    }
+
+    /// Registers a [`BlockRewardProvider`] that supplies the block and uncle rewards of the reward
+    /// traces, e.g. for chains with a different reward schedule than Ethereum.
+    ///
+    /// Without a provider, the rewards are calculated with [`EthereumBlockRewards`] of the chain
+    /// spec. This replaces any previously registered provider and applies to all clones of this
+    /// [`TraceApi`].
+    pub fn register_block_rewards(&self, block_rewards: Arc<dyn BlockRewardProvider>) {
+        *self.inner.block_rewards.write() = Some(block_rewards);
+    }
+
+    /// Returns an error if the target of the call isn't in the
+    /// [`TraceApiBuilder::address_allowlist`].
+    fn check_allowed_call(&self, call: &TransactionRequest) -> Result<(), EthApiError> {
+        check_allowed_call_target(self.inner.address_allowlist.as_deref(), call.to)
+    }
+
+    /// Acquires a permit to execute a tracing call.
+    async fn acquire_trace_permit(
+        &self,
+    ) -> std::result::Result<OwnedSemaphorePermit, AcquireError> {
+        self.inner.blocking_task_guard.clone().acquire_owned().await
+    }
+
+    /// Awaits the given tracing future, aborting it with [`EthApiError::ExecutionTimedOut`] once
+    /// the [`EthConfig::trace_call_timeout`] expires.
+    ///
+    /// Aborting drops the future and with it any trace permit it holds. Work that has already
+    /// been spawned to the blocking pool runs to completion, but its result is discarded.
+    async fn with_trace_timeout<T>(
+        &self,
+        fut: impl Future<Output = Result<T, Eth::Error>>,
+    ) -> Result<T, Eth::Error> {
+        with_timeout(self.inner.eth_config.trace_call_timeout, fut).await
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Returns the methods, output formats, overrides and limits supported by this [`TraceApi`].
+    ///
+    /// The methods are the ones registered by the [`TraceApiServer`] implementation and the limits
+    /// are taken from the [`EthConfig`] of this [`TraceApi`].
+    pub fn trace_capabilities(&self) -> TraceCapabilities
+    where
+        Self: TraceApiServer,
+    {
+        let module = self.clone().into_rpc();
+        trace_capabilities(module.method_names(), &self.inner.eth_config)
+    }
+}
+
+impl<Eth: RpcNodeCore> TraceApi<Eth> {
+    /// Access the underlying provider.
+    pub fn provider(&self) -> &Eth::Provider {
+        self.inner.eth_api.provider()
+    }
+}
+
+// === impl TraceApi === //
+
+impl<Eth> TraceApi<Eth>
+where
+    // tracing methods do _not_ read from mempool, hence no `LoadBlock` trait
+    // bound
+    Eth: Trace + Call + LoadPendingBlock + LoadTransaction + 'static,
+{
+    /// Executes the given call and returns a number of possible traces for it.
+    ///
+    /// Code overrides in the request's state overrides are validated before execution, see
+    /// [`validate_code_overrides`]. Overriding an account's code does not touch its storage, so
+    /// existing slots remain in place unless they are also overridden via `state` or `stateDiff`.
+    pub async fn trace_call(
+        &self,
+        trace_request: TraceCallRequest,
+    ) -> Result<TraceResults, Eth::Error> {
+        self.trace_call_with_options(trace_request, TraceCallOptions::default())
+            .await
+            .map(|res| res.results)
+    }
+
+    /// Executes the given call with additional [`TraceCallOptions`] and returns a number of
+    /// possible traces for it, along with the requested diagnostics.
+    ///
+    /// A malformed EIP-7702 authorization list is rejected with
+    /// [`EthApiError::InvalidParams`], as is a gas limit above the block gas limit if
+    /// [`TraceCallOptions::enforce_block_gas_limit`] is set, and a call to an address outside of
+    /// the [`TraceApiBuilder::address_allowlist`].
+    pub async fn trace_call_with_options(
+        &self,
+        mut trace_request: TraceCallRequest,
+        options: TraceCallOptions,
+    ) -> Result<TraceCallResults, Eth::Error> {
+        self.check_allowed_call(&trace_request.call)?;
+        let at = trace_request.block_id.unwrap_or_default();
+        if let Some(state_overrides) = &trace_request.state_overrides {
+            // the code size limit depends on the hardforks active at the target block, if known
+            let max_code_size =
+                self.provider().header_by_id(at).map_err(Eth::Error::from_eth_err)?.and_then(
+                    |header| {
+                        max_code_size::<<Eth::Evm as ConfigureEvm>::Primitives>(
+                            self.provider().chain_spec(),
+                            &header,
+                        )
+                    },
+                );
+            validate_code_overrides(state_overrides, max_code_size)?;
+        }
+        validate_authorization_list(&trace_request.call)?;
+        if let Some(chain_id) = options.chain_id_override {
+            trace_request.call.chain_id = Some(chain_id);
+        }
+        let authorization_list =
+            trace_request.call.authorization_list.clone().filter(|_| options.authorizations);
+        if options.enforce_block_gas_limit {
+            let block_gas_limit =
+                match trace_request.block_overrides.as_ref().and_then(|o| o.gas_limit) {
+                    Some(gas_limit) => gas_limit,
+                    None => self.eth_api().evm_env_at(at).await?.0.block_env.gas_limit,
+                };
+            check_block_gas_limit(trace_request.call.gas, block_gas_limit)?;
+        }
+        let config = options.tracing_config(&trace_request.trace_types);
+        let overrides =
+            EvmOverrides::new(trace_request.state_overrides, trace_request.block_overrides);
+        let custom = options
+            .custom_inspector
+            .then(|| self.inner.custom_inspector.read().as_ref().map(|factory| factory()))
+            .flatten();
+        // custom inspectors are only available via the Rust API, so the permit is acquired here
+        // rather than by the RPC handler
+        let _permit = match custom {
+            Some(_) => Some(self.acquire_trace_permit().await),
+            None => None,
+        };
+        let mut inspector = TraceCallInspector {
+            tracing: TracingInspector::new(config),
+            opcodes: options.opcode_counts.then(OpcodeGasInspector::default),
+            custom,
+        };
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_call_at(trace_request.call, at, overrides, move |db, evm_env, tx_env| {
+                // wrapper is hack to get around 'higher-ranked lifetime error', see
+                // <https://github.com/rust-lang/rust/issues/100013>
+                let db = db.0;
+                let mut evm_env = evm_env;
+                override_chain_id(&mut evm_env, options.chain_id_override);
+
+                let authorizations = authorization_list
+                    .map(|list| {
+                        authorization_outcomes(
+                            &mut *db,
+                            evm_env.cfg_env.chain_id,
+                            tx_env.caller(),
+                            &list,
+                        )
+                    })
+                    .transpose()
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let pre_state = options
+                    .pre_state
+                    .then(|| {
+                        let accounts = pre_state_accounts(
+                            tx_env.caller(),
+                            tx_env.kind(),
+                            options.pre_state_recipient,
+                        );
+                        account_snapshots(&mut *db, &accounts)
+                    })
+                    .transpose()
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let intrinsic_gas = options.gas_split.then(|| {
+                    intrinsic_gas(
+                        &*this.provider().chain_spec(),
+                        evm_env.block_env.number.saturating_to(),
+                        evm_env.block_env.timestamp.saturating_to(),
+                        tx_env.clone(),
+                    )
+                });
+
+                let gas_limit = tx_env.gas_limit();
+                let (res, _) = this.eth_api().inspect(&mut *db, evm_env, tx_env, &mut inspector)?;
+                let gas_split = intrinsic_gas.map(|intrinsic_gas| {
+                    GasSplit::new(intrinsic_gas, &inspector.tracing, &res.result)
+                });
+                let gas_info =
+                    options.gas_info.then(|| GasInfo::new::<Eth::Error, _>(gas_limit, &res.result));
+                let logs =
+                    options.logs.then(|| trace_call_logs(res.result.logs(), options.decode_events));
+                let opcode_counts = inspector.opcode_counts();
+                let custom = inspector.custom.take().map(|custom| custom.into_json());
+                let results = inspector
+                    .tracing
+                    .into_parity_builder()
+                    .into_trace_results_with_state(&res, &trace_request.trace_types, &db)
+                    .map_err(Eth::Error::from_eth_err)?;
+                let delegations = options
+                    .delegations
+                    .then(|| call_delegations(&results.trace, &res.state, &db))
+                    .transpose()
+                    .map_err(Eth::Error::from_eth_err)?;
+                let storage_diff = options
+                    .storage_diff
+                    .then(|| storage_diff_of(results.state_diff.as_ref(), &res.state, &db))
+                    .transpose()
+                    .map_err(Eth::Error::from_eth_err)?;
+                let precompiles = options.precompiles.then(|| precompile_calls(&results.trace));
+                Ok(TraceCallResults {
+                    results,
+                    authorizations,
+                    opcode_counts,
+                    custom,
+                    pre_state,
+                    logs,
+                    gas_split,
+                    gas_info,
+                    storage_diff,
+                    delegations,
+                    precompiles,
+                })
+            })
+            .await
+    }
+
+    /// Traces a call to `eth_sendRawTransaction` without making the call, returning the traces.
+    ///
+    /// The given block overrides are applied to the block environment before the transaction is
+    /// executed, e.g. to simulate it with a different base fee or timestamp.
+    pub async fn trace_raw_transaction(
+        &self,
+        tx: Bytes,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> Result<TraceResults, Eth::Error> {
+        let tx =
+            recover_raw_transaction::<PooledTxTy<<Eth::Evm as ConfigureEvm>::Primitives>>(&tx)?
+                .map(Into::<TxTy<<Eth::Evm as ConfigureEvm>::Primitives>>::into);
+
+        let (mut evm_env, at) = self.eth_api().evm_env_at(block_id.unwrap_or_default()).await?;
+        let tx_env = self.eth_api().evm_config().tx_env(tx);
+
+        let config = TracingInspectorConfig::from_parity_config(&trace_types);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(at, move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                if let Some(block_overrides) = block_overrides {
+                    apply_block_overrides(*block_overrides, &mut db, &mut evm_env.block_env);
+                }
+                let mut inspector = TracingInspector::new(config);
+                let (res, _) = this.eth_api().inspect(&mut db, evm_env, tx_env, &mut inspector)?;
+                inspector
+                    .into_parity_builder()
+                    .into_trace_results_with_state(&res, &trace_types, &db)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Traces multiple raw transactions on top of the same block.
+    ///
+    /// Unlike [`Self::trace_call_many`], the transactions are independent: each transaction is
+    /// traced against the state at the given block, unaffected by the other transactions.
+    ///
+    /// Returns an error naming the index of the first transaction that fails to decode.
+    pub async fn trace_raw_transaction_many(
+        &self,
+        txs: Vec<Bytes>,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<TraceResults>, Eth::Error> {
+        let txs = txs
+            .iter()
+            .enumerate()
+            .map(|(idx, tx)| {
+                recover_raw_transaction::<PooledTxTy<<Eth::Evm as ConfigureEvm>::Primitives>>(tx)
+                    .map(|tx| tx.map(Into::<TxTy<<Eth::Evm as ConfigureEvm>::Primitives>>::into))
+                    .map_err(|err| {
+                        EthApiError::InvalidParams(format!(
+                            "invalid transaction at index {idx}: {err}"
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (evm_env, at) = self.eth_api().evm_env_at(block_id.unwrap_or_default()).await?;
+        let tx_envs =
+            txs.into_iter().map(|tx| self.eth_api().evm_config().tx_env(tx)).collect::<Vec<_>>();
+
+        let config = TracingInspectorConfig::from_parity_config(&trace_types);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(at, move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut results = Vec::with_capacity(tx_envs.len());
+                for tx_env in tx_envs {
+                    // the state changes of a transaction are never committed, so every
+                    // transaction executes against the state of the block
+                    let mut inspector = TracingInspector::new(config);
+                    let (res, _) =
+                        this.eth_api().inspect(&mut db, evm_env.clone(), tx_env, &mut inspector)?;
+                    let trace_res = inspector
+                        .into_parity_builder()
+                        .into_trace_results_with_state(&res, &trace_types, &db)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    results.push(trace_res);
+                }
+                Ok(results)
+            })
+            .await
+    }
+
+    /// Performs multiple call traces on top of the same block. i.e. transaction n will be executed
+    /// on top of a pending block with all n-1 transactions applied (traced) first.
+    ///
+    /// Note: Allows tracing dependent transactions, hence all transactions are traced in sequence
+    pub async fn trace_call_many(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<TraceResults>, Eth::Error> {
+        self.trace_call_many_with_options(calls, block_id, TraceCallManyOptions::default())
+            .await
+            .map(|res| res.results)
+    }
+
+    /// Performs multiple call traces on top of the same block like [`Self::trace_call_many`],
+    /// configured by the given [`TraceCallManyOptions`].
+    ///
+    /// With [`TraceCallManyOptions::snapshot_accounts`], the balance and nonce of the given
+    /// accounts are recorded after each call has been applied. With
+    /// [`TraceCallManyOptions::gas_limits`], the gas limit of individual calls is overridden. With
+    /// [`TraceCallManyOptions::independent`], each call is executed on top of the block state
+    /// only.
+    pub async fn trace_call_many_with_options(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+        options: TraceCallManyOptions,
+    ) -> Result<TraceCallManyResults, Eth::Error> {
+        let calls = calls
+            .into_iter()
+            .map(|(call, trace_types)| (call, trace_types, EvmOverrides::default()))
+            .collect();
+        self.trace_call_many_with_overrides(calls, block_id, options).await
+    }
+
+    /// Performs multiple call traces on top of the same block like
+    /// [`Self::trace_call_many_with_options`], with the given [`EvmOverrides`] applied to each
+    /// call.
+    ///
+    /// The state overrides of a call are applied on top of the state changes of the previous
+    /// calls and remain in place for the following calls. With
+    /// [`TraceCallManyOptions::independent`], they only apply to the call itself. The block
+    /// overrides of a call only apply to the call itself.
+    pub async fn trace_call_many_with_overrides(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>, EvmOverrides)>,
+        block_id: Option<BlockId>,
+        options: TraceCallManyOptions,
+    ) -> Result<TraceCallManyResults, Eth::Error> {
+        check_call_many_batch(calls.len(), self.inner.eth_config.max_trace_call_many)?;
+        for (call, _, _) in &calls {
+            self.check_allowed_call(call)?;
+        }
+        let at = block_id.unwrap_or(BlockId::pending());
+        let (evm_env, at) = self.eth_api().evm_env_at(at).await?;
+
+        let this = self.clone();
+        // execute all transactions on top of each other and record the traces
+        self.eth_api()
+            .spawn_with_state_at_block(at, move |state| {
+                let mut results = Vec::with_capacity(calls.len());
+                let mut snapshots = Vec::new();
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                let mut calls = calls.into_iter().enumerate().peekable();
+
+                while let Some((idx, call)) = calls.next() {
+                    let has_next = calls.peek().is_some();
+                    let (trace_res, snapshot) = if options.independent {
+                        // the overrides of an independent call must not leak into the next calls
+                        let mut call_db = CacheDB::new(&db);
+                        this.trace_call_many_entry(
+                            &mut call_db,
+                            evm_env.clone(),
+                            idx,
+                            call,
+                            &options,
+                            has_next,
+                        )?
+                    } else {
+                        this.trace_call_many_entry(
+                            &mut db,
+                            evm_env.clone(),
+                            idx,
+                            call,
+                            &options,
+                            has_next,
+                        )?
+                    };
+
+                    results.push(trace_res);
+                    snapshots.extend(snapshot);
+                }
+
+                Ok(TraceCallManyResults { results, snapshots })
+            })
+            .await
+    }
+
+    /// Traces the call at index `idx` of [`Self::trace_call_many_with_overrides`] on top of the
+    /// given database and applies its state changes according to the [`TraceCallManyOptions`].
+    ///
+    /// Returns the traces and, if requested, the snapshot of the accounts after the call.
+    fn trace_call_many_entry<DB>(
+        &self,
+        db: &mut CacheDB<DB>,
+        evm_env: EvmEnvFor<Eth::Evm>,
+        idx: usize,
+        (call, trace_types, overrides): (TransactionRequest, HashSet<TraceType>, EvmOverrides),
+        options: &TraceCallManyOptions,
+        has_next: bool,
+    ) -> Result<(TraceResults, Option<Vec<AccountSnapshot>>), Eth::Error>
+    where
+        DB: DatabaseRef<Error = ProviderError>,
+    {
+        let (evm_env, mut tx_env) =
+            self.eth_api().prepare_call_env(evm_env, call, &mut *db, overrides)?;
+        if let Some(gas_limit) = options.gas_limit(idx) {
+            tx_env.set_gas_limit(gas_limit);
+        }
+        let config = TracingInspectorConfig::from_parity_config(&trace_types);
+        let mut inspector = TracingInspector::new(config);
+        let (res, _) = self.eth_api().inspect(&mut *db, evm_env, tx_env, &mut inspector)?;
+
+        let trace_res = inspector
+            .into_parity_builder()
+            .into_trace_results_with_state(&res, &trace_types, &*db)
+            .map_err(Eth::Error::from_eth_err)?;
+
+        let snapshot = apply_call_many_state(db, res.state, options, has_next)
+            .map_err(Eth::Error::from_eth_err)?;
+        Ok((trace_res, snapshot))
+    }
+
+    /// Performs multiple call traces independently of each other, i.e. each call is executed on
+    /// top of the same block state, without the state changes of the previous calls.
+    ///
+    /// The results are in the same order as the calls. An empty batch is rejected with
+    /// [`EthApiError::InvalidParams`].
+    pub async fn trace_call_many_independent(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<TraceResults>, Eth::Error> {
+        if calls.is_empty() {
+            return Err(EthApiError::InvalidParams("empty batch of calls".to_string()).into())
+        }
+        let options = TraceCallManyOptions::default().with_independent();
+        self.trace_call_many_with_options(calls, block_id, options).await.map(|res| res.results)
+    }
+
+    /// Performs multiple call traces independently of each other like
+    /// [`Self::trace_call_many_independent`], but traces the calls in parallel.
+    ///
+    /// Each call is traced in its own blocking task on top of the same block state. The batch
+    /// holds a single tracing permit, and at most
+    /// [`EthConfig::max_trace_call_many_concurrency`] of its calls are traced at once. The whole
+    /// batch is subject to the [`EthConfig::trace_call_timeout`].
+    ///
+    /// The results are in the same order as the calls. An empty batch is rejected with
+    /// [`EthApiError::InvalidParams`].
+    pub async fn trace_call_many_parallel(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<TraceResults>, Eth::Error> {
+        if calls.is_empty() {
+            return Err(EthApiError::InvalidParams("empty batch of calls".to_string()).into())
+        }
+        check_call_many_batch(calls.len(), self.inner.eth_config.max_trace_call_many)?;
+        for (call, _) in &calls {
+            self.check_allowed_call(call)?;
+        }
+        let _permit = self.acquire_trace_permit().await;
+        self.with_trace_timeout(self.trace_calls_in_parallel(calls, block_id)).await
+    }
+
+    /// Traces the given calls in parallel on top of the same block state, see
+    /// [`Self::trace_call_many_parallel`].
+    async fn trace_calls_in_parallel(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<TraceResults>, Eth::Error> {
+        let at = block_id.unwrap_or(BlockId::pending());
+        let (evm_env, at) = self.eth_api().evm_env_at(at).await?;
+
+        let traces = calls
+            .into_iter()
+            .map(|(call, trace_types)| {
+                let this = self.clone();
+                let evm_env = evm_env.clone();
+                // the call is only spawned once it's polled, i.e. within the concurrency limit
+                async move {
+                    let tracer = this.clone();
+                    this.eth_api()
+                        .spawn_with_state_at_block(at, move |state| {
+                            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                            let (evm_env, tx_env) = tracer.eth_api().prepare_call_env(
+                                evm_env,
+                                call,
+                                &mut db,
+                                Default::default(),
+                            )?;
+                            let config = TracingInspectorConfig::from_parity_config(&trace_types);
+                            let mut inspector = TracingInspector::new(config);
+                            let (res, _) = tracer.eth_api().inspect(
+                                &mut db,
+                                evm_env,
+                                tx_env,
+                                &mut inspector,
+                            )?;
+                            inspector
+                                .into_parity_builder()
+                                .into_trace_results_with_state(&res, &trace_types, &db)
+                                .map_err(Eth::Error::from_eth_err)
+                        })
+                        .await
+                }
+            })
+            .collect();
+        try_join_all_limited(traces, self.inner.eth_config.max_trace_call_many_concurrency).await
+    }
+
+    /// Replays a transaction, returning the traces.
+    ///
+    /// If only [`TraceType::StateDiff`] is requested, the transaction is executed without tracing
+    /// since the state diff is derived from the resulting state alone.
+    pub async fn replay_transaction(
+        &self,
+        hash: B256,
+        trace_types: HashSet<TraceType>,
+    ) -> Result<TraceResults, Eth::Error> {
+        if is_state_diff_only(&trace_types) {
+            // the state diff is derived from the execution result alone, no need to trace
+            return self
+                .eth_api()
+                .spawn_trace_transaction_in_block_with_inspector(
+                    hash,
+                    NoOpInspector {},
+                    move |_, _, res, db| {
+                        state_diff_trace_results(&res, &db).map_err(Eth::Error::from_eth_err)
+                    },
+                )
+                .await
+                .transpose()
+                .ok_or(EthApiError::TransactionNotFound)?
+        }
+
+        let config = TracingInspectorConfig::from_parity_config(&trace_types);
+        self.eth_api()
+            .spawn_trace_transaction_in_block(hash, config, move |_, inspector, res, db| {
+                let trace_res = inspector
+                    .into_parity_builder()
+                    .into_trace_results_with_state(&res, &trace_types, &db)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(trace_res)
+            })
+            .await
+            .transpose()
+            .ok_or(EthApiError::TransactionNotFound)?
+    }
+
+    /// Replays a transaction like [`Self::replay_transaction`], but on top of the state at the
+    /// given block instead of the block the transaction was included in.
+    ///
+    /// The transaction is executed in the environment of the `at` block, after all of its
+    /// transactions have been applied. Since the state differs, the nonce of the transaction is
+    /// not checked.
+    pub async fn replay_transaction_at(
+        &self,
+        hash: B256,
+        trace_types: HashSet<TraceType>,
+        at: BlockId,
+    ) -> Result<TraceResults, Eth::Error> {
+        let tx = self
+            .eth_api()
+            .transaction_by_hash(hash)
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?
+            .into_recovered();
+
+        let (mut evm_env, at) = self.eth_api().evm_env_at(at).await?;
+        evm_env.cfg_env.disable_nonce_check = true;
+        let tx_env = self.eth_api().evm_config().tx_env(tx);
+
+        let config = TracingInspectorConfig::from_parity_config(&trace_types);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(at, move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut inspector = TracingInspector::new(config);
+                let (res, _) = this.eth_api().inspect(&mut db, evm_env, tx_env, &mut inspector)?;
+                inspector
+                    .into_parity_builder()
+                    .into_trace_results_with_state(&res, &trace_types, &db)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns transaction trace objects at the given index
+    ///
+    /// Note: For compatibility reasons this only supports 1 single index, since this method is
+    /// supposed to return a single trace. See also: <https://github.com/ledgerwatch/erigon/blob/862faf054b8a0fa15962a9c73839b619886101eb/turbo/jsonrpc/trace_filtering.go#L114-L133>
+    ///
+    /// This returns `None` if `indices` is empty or the trace object at the index does not exist,
+    /// and an [`EthApiError::InvalidParams`] error if more than one index is given. Use
+    /// [`Self::trace_get_many`] to get the trace objects at multiple indices.
+    pub async fn trace_get(
+        &self,
+        hash: B256,
+        indices: Vec<usize>,
+    ) -> Result<Option<LocalizedTransactionTrace>, Eth::Error> {
+        let Some(index) = single_trace_index(&indices)? else { return Ok(None) };
+        self.trace_get_index(hash, index).await
+    }
+
+    /// Returns the transaction trace objects at the given indices, in the same order as the
+    /// indices.
+    ///
+    /// Unlike [`Self::trace_get`], this supports any number of indices. The transaction is only
+    /// traced once. An entry is `None` if the trace object at that index does not exist, all
+    /// entries are `None` if the transaction does not exist.
+    pub async fn trace_get_many(
+        &self,
+        hash: B256,
+        indices: Vec<usize>,
+    ) -> Result<Vec<Option<LocalizedTransactionTrace>>, Eth::Error> {
+        let traces = self.trace_transaction(hash).await?.unwrap_or_default();
+        Ok(traces_at_indices(&traces, &indices))
+    }
+
+    /// Returns transaction trace object at the given index.
+    ///
+    /// Returns `None` if the trace object at that index does not exist
+    pub async fn trace_get_index(
+        &self,
+        hash: B256,
+        index: usize,
+    ) -> Result<Option<LocalizedTransactionTrace>, Eth::Error> {
+        Ok(self.trace_transaction(hash).await?.and_then(|traces| traces.into_iter().nth(index)))
+    }
+
+    /// Returns all traces for the given transaction hash
+    ///
+    /// The traces of mined transactions are cached if enabled via
+    /// [`EthConfig::trace_transaction_cache_size()`].
+    ///
+    /// Fails if the transaction has more traces than [`EthConfig::max_traces_per_response()`].
+    pub async fn trace_transaction(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
+        let Some(cache) = &self.inner.transaction_traces else {
+            return self.trace_transaction_uncached(hash).await
+        };
+
+        // pending transactions are not cached, their block isn't final
+        let Some((_, BlockId::Hash(block_hash))) =
+            self.eth_api().transaction_by_hash_at(hash).await?
+        else {
+            return self.trace_transaction_uncached(hash).await
+        };
+        let block_hash = block_hash.block_hash;
+        if let Some(traces) = cache.get(hash, block_hash) {
+            return Ok(Some(traces))
+        }
+
+        let traces = self.trace_transaction_uncached(hash).await?;
+        if let Some(traces) = &traces {
+            // the transaction may have been reorged into another block in the meantime
+            if traces.first().is_some_and(|trace| trace.block_hash == Some(block_hash)) {
+                cache.insert(hash, block_hash, traces.clone());
+            }
+        }
+        Ok(traces)
+    }
+
+    /// Returns the traces of the given transaction that failed, along with their revert reasons.
+    ///
+    /// These are the traces with an `error`, e.g. subcalls that reverted while the transaction
+    /// continued, see [`RevertedTrace`]. Built from [`Self::trace_transaction`], so the traces
+    /// are cached if enabled.
+    pub async fn trace_transaction_reverts(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Vec<RevertedTrace>>, Eth::Error> {
+        let traces = self.trace_transaction(hash).await?;
+        Ok(traces.map(|traces| traces.into_iter().filter_map(RevertedTrace::new).collect()))
+    }
+
+    /// Returns all traces of the given transaction, each along with the logs emitted by its
+    /// frame, see [`TransactionTraceWithLogs`].
+    ///
+    /// The logs are attributed to the frame executing the `LOG` opcode, so the logs of subcalls
+    /// are returned with the subcall's trace. The result isn't cached, see
+    /// [`Self::trace_transaction`].
+    pub async fn trace_transaction_with_logs(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Vec<TransactionTraceWithLogs>>, Eth::Error> {
+        let inspector = FrameLogsInspector::new(TracingInspector::new(
+            TracingInspectorConfig::default_parity(),
+        ));
+        self.eth_api()
+            .spawn_trace_transaction_in_block_with_inspector(
+                hash,
+                inspector,
+                move |tx_info, inspector, _, _| Ok(inspector.into_traces_with_logs(tx_info)),
+            )
+            .await
+    }
+
+    /// Returns the trace of the top-level call of the given transaction, i.e. the trace with an
+    /// empty trace address.
+    ///
+    /// The transaction is traced in full, so the subcalls are still recorded by the inspector and
+    /// count towards the `subtraces` of the top-level trace. Only the conversion into localized
+    /// traces stops after the top-level call. The result isn't cached, see
+    /// [`Self::trace_transaction`].
+    pub async fn trace_transaction_top_level(
+        &self,
+        hash: B256,
+    ) -> Result<Option<LocalizedTransactionTrace>, Eth::Error> {
+        Ok(self
+            .eth_api()
+            .spawn_trace_transaction_in_block(
+                hash,
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _| {
+                    // the traces are yielded in depth-first order, starting with the top-level call
+                    Ok(inspector
+                        .into_parity_builder()
+                        .into_localized_transaction_traces_iter(tx_info)
+                        .next())
+                },
+            )
+            .await?
+            .flatten())
+    }
+
+    /// Returns the traces of the given transaction like [`Self::trace_transaction`], nested into
+    /// a call tree by their trace addresses.
+    pub async fn trace_transaction_tree(
+        &self,
+        hash: B256,
+    ) -> Result<Option<CallTreeNode>, Eth::Error> {
+        Ok(self.trace_transaction(hash).await?.and_then(call_tree))
+    }
+
+    /// Traces the given transaction like [`Self::trace_transaction`], bypassing the cache.
+    async fn trace_transaction_uncached(
+        &self,
+        hash: B256,
+    ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
+        let max_traces = self.inner.eth_config.max_traces_per_response;
+        self.eth_api()
+            .spawn_trace_transaction_in_block(
+                hash,
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _| {
+                    check_trace_limit(inspector.traces().nodes().len(), max_traces)?;
+                    let traces =
+                        inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
+                    Ok(traces)
+                },
+            )
+            .await
+    }
+
+    /// Returns the geth style struct logs of the given transaction, i.e. the program counter,
+    /// opcode, gas and, depending on the given options, the stack, memory and storage of each
+    /// executed step.
+    ///
+    /// This is the same as the default struct log tracer of `debug_traceTransaction`. Recording
+    /// the memory of each step is expensive and disabled unless
+    /// [`GethDefaultTracingOptions::enable_memory`] is set.
+    pub async fn trace_transaction_steps(
+        &self,
+        hash: B256,
+        opts: GethDefaultTracingOptions,
+    ) -> Result<Option<DefaultFrame>, Eth::Error> {
+        let Some(tx) = self.eth_api().transaction_by_hash(hash).await? else { return Ok(None) };
+        let tx_gas_limit = tx.into_recovered().gas_limit();
+
+        self.eth_api()
+            .spawn_trace_transaction_in_block(
+                hash,
+                TracingInspectorConfig::from_geth_config(&opts),
+                move |_, inspector, res, _| {
+                    let gas_used = res.result.gas_used();
+                    let return_value = res.result.into_output().unwrap_or_default();
+                    Ok(inspector
+                        .with_transaction_gas_limit(tx_gas_limit)
+                        .into_geth_builder()
+                        .geth_traces(gas_used, return_value, opts))
+                },
+            )
+            .await
+    }
+
+    /// Returns all traces for the given transaction hash, along with the gas used, status and
+    /// output of the transaction's execution.
+    ///
+    /// This saves a separate receipt lookup when correlating the traces with the outcome of the
+    /// transaction.
+    pub async fn trace_transaction_with_receipt(
+        &self,
+        hash: B256,
+    ) -> Result<Option<TransactionTracesWithReceipt>, Eth::Error> {
+        self.eth_api()
+            .spawn_trace_transaction_in_block(
+                hash,
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, res, _| {
+                    let traces =
+                        inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
+                    Ok(TransactionTracesWithReceipt {
+                        traces,
+                        gas_used: res.result.gas_used(),
+                        status: res.result.is_success(),
+                        output: res.result.output().cloned().unwrap_or_default(),
+                    })
+                },
+            )
+            .await
+    }
+
+    /// Returns all traces for the given transaction hash, configured by the given
+    /// [`TraceTransactionOptions`].
+    pub async fn trace_transaction_with_options(
+        &self,
+        hash: B256,
+        options: TraceTransactionOptions,
+    ) -> Result<Option<Vec<TransactionTraceFrame>>, Eth::Error> {
+        let traces = self.trace_transaction(hash).await?;
+        Ok(traces.map(|traces| {
+            traces
+                .into_iter()
+                .map(|trace| TransactionTraceFrame {
+                    gas_at_entry: options
+                        .gas_at_entry
+                        .then(|| gas_at_entry(&trace.trace))
+                        .flatten(),
+                    precompile: options
+                        .precompiles
+                        .then(|| Precompile::of_trace(&trace.trace))
+                        .flatten(),
+                    trace,
+                })
+                .collect()
+        }))
+    }
+
+    /// Returns all opcodes with their count and combined gas usage for the given transaction in no
+    /// particular order.
+    pub async fn trace_transaction_opcode_gas(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TransactionOpcodeGas>, Eth::Error> {
+        self.eth_api()
+            .spawn_trace_transaction_in_block_with_inspector(
+                tx_hash,
+                OpcodeGasInspector::default(),
+                move |_tx_info, inspector, _res, _| {
+                    let trace = TransactionOpcodeGas {
+                        transaction_hash: tx_hash,
+                        opcode_gas: inspector.opcode_gas_iter().collect(),
+                    };
+                    Ok(trace)
+                },
+            )
+            .await
+    }
+
+    /// Returns the opcode gas of the given transaction like [`Self::trace_transaction_opcode_gas`],
+    /// along with the count and gas usage aggregated by [`OpcodeCategory`].
+    pub async fn trace_transaction_opcode_gas_categorized(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TransactionOpcodeGasCategorized>, Eth::Error> {
+        self.eth_api()
+            .spawn_trace_transaction_in_block_with_inspector(
+                tx_hash,
+                OpcodeGasInspector::default(),
+                move |_tx_info, inspector, _res, _| {
+                    Ok(TransactionOpcodeGasCategorized::new(tx_hash, inspector.opcode_iter()))
+                },
+            )
+            .await
+    }
+
+    /// Returns the opcode gas of the given transaction like [`Self::trace_transaction_opcode_gas`],
+    /// broken down by the contract executing the opcodes.
+    ///
+    /// This attributes the gas of nested calls to the called contracts instead of aggregating it
+    /// over the whole transaction.
+    pub async fn trace_transaction_opcode_gas_by_address(
+        &self,
+        tx_hash: B256,
+    ) -> Result<Option<TransactionOpcodeGasByAddress>, Eth::Error> {
+        self.eth_api()
+            .spawn_trace_transaction_in_block_with_inspector(
+                tx_hash,
+                OpcodeGasByAddressInspector::default(),
+                move |_tx_info, inspector, _res, _| {
+                    Ok(inspector.into_transaction_opcode_gas(tx_hash))
+                },
+            )
+            .await
+    }
+
+    /// Returns the registered [`BlockRewardProvider`], or the [`EthereumBlockRewards`] of the chain
+    /// spec if none is registered.
+    fn block_rewards(&self) -> Arc<dyn BlockRewardProvider> {
+        match self.inner.block_rewards.read().as_ref() {
+            Some(block_rewards) => block_rewards.clone(),
+            None => Arc::new(EthereumBlockRewards::new(self.provider().chain_spec())),
+        }
+    }
+
+    /// Calculates the base block reward for the given block, see
+    /// [`BlockRewardProvider::base_block_reward`].
+    fn calculate_base_block_reward<H: BlockHeader>(
+        &self,
+        header: &H,
+    ) -> Result<Option<u128>, Eth::Error> {
+        Ok(self.block_rewards().base_block_reward(header.number()))
+    }
+
+    /// Extracts the reward traces for the given block:
+    ///  - block reward
+    ///  - uncle rewards
+    ///
+    /// The block reward is credited to [`NodePrimitives::reward_recipient`].
+    fn extract_reward_traces(
+        &self,
+        header: &ProviderHeader<Eth::Provider>,
+        ommers: Option<&[OmmerTy<<Eth::Evm as ConfigureEvm>::Primitives>]>,
+        base_block_reward: u128,
+    ) -> Vec<LocalizedTransactionTrace> {
+        let ommers_cnt = ommers.map(|o| o.len()).unwrap_or_default();
+        let mut traces = Vec::with_capacity(ommers_cnt + 1);
+
+        let chain_spec = self.provider().chain_spec();
+        let block_rewards = self.block_rewards();
+        let block_reward = block_rewards.block_reward(base_block_reward, ommers_cnt);
+        traces.push(reward_trace(
+            header,
+            RewardAction {
+                author: <Eth::Evm as ConfigureEvm>::Primitives::reward_recipient(
+                    header,
+                    &chain_spec,
+                ),
+                reward_type: RewardType::Block,
+                value: U256::from(block_reward),
+            },
+        ));
+
+        let Some(ommers) = ommers else { return traces };
+
+        for uncle in ommers {
+            let uncle_reward =
+                block_rewards.ommer_reward(base_block_reward, header.number(), uncle.number());
+            traces.push(reward_trace(
+                header,
+                RewardAction {
+                    author: uncle.beneficiary(),
+                    reward_type: RewardType::Uncle,
+                    value: U256::from(uncle_reward),
+                },
+            ));
+        }
+        traces
+    }
+}
+
+impl<Eth> TraceApi<Eth>
+where
+    // tracing methods read from mempool, hence `LoadBlock` trait bound via
+    // `TraceExt`
+    Eth: TraceExt + 'static,
+{
+    /// Traces the call as if it was included in the given block at position `tx_index`, i.e. on
+    /// top of the state after the block's transactions `0..tx_index`.
+    ///
+    /// The call is executed in the environment of the block. `tx_index` may be the number of the
+    /// block's transactions to trace the call on top of all of them, larger indices are rejected
+    /// with [`EthApiError::InvalidParams`].
+    pub async fn trace_call_at_tx_index(
+        &self,
+        call: TransactionRequest,
+        trace_types: HashSet<TraceType>,
+        block_id: BlockId,
+        tx_index: usize,
+    ) -> Result<TraceResults, Eth::Error> {
+        self.check_allowed_call(&call)?;
+        let block = self
+            .eth_api()
+            .recovered_block(block_id)
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        check_tx_position(tx_index, block.body().transaction_count())?;
+        let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+
+                // replay the transactions preceding the call
+                let evm_config = this.eth_api().evm_config();
+                let mut evm = evm_config.evm_with_env(&mut db, evm_env.clone());
+                for tx in block.transactions_recovered().take(tx_index) {
+                    evm.transact_commit(evm_config.tx_env(tx)).map_err(Eth::Error::from_evm_err)?;
+                }
+                drop(evm);
+
+                let (evm_env, tx_env) = this.eth_api().prepare_call_env(
+                    evm_env,
+                    call,
+                    &mut db,
+                    EvmOverrides::default(),
+                )?;
+                let config = TracingInspectorConfig::from_parity_config(&trace_types);
+                let mut inspector = TracingInspector::new(config);
+                let (res, _) = this.eth_api().inspect(&mut db, evm_env, tx_env, &mut inspector)?;
+                inspector
+                    .into_parity_builder()
+                    .into_trace_results_with_state(&res, &trace_types, &db)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns all transaction traces that match the given filter.
+    ///
+    /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
+    /// the filter.
+    ///
+    /// The `after` and `count` fields of the filter are applied to individual traces, see
+    /// [`Self::trace_filter_with_options`] for paging by whole transactions instead. Reward traces
+    /// are only matched if enabled via [`EthConfig::include_reward_traces()`].
+    pub async fn trace_filter(
+        &self,
+        filter: TraceFilter,
+    ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
+        self.trace_filter_with_options(filter, TraceFilterOptions::default()).await
+    }
+
+    /// Returns all transaction traces that match the given filter, configured by the given
+    /// [`TraceFilterOptions`].
+    ///
+    /// By default the traces are ordered by block number. The traces of a block are ordered by
+    /// transaction index, the traces of a transaction by trace address in depth-first order, and
+    /// the block's reward traces follow its transaction traces. With
+    /// [`TraceFilterOrder::GasUsed`], the traces are sorted by gas used instead, before `after`
+    /// and `count` are applied.
+    ///
+    /// With [`TraceFilterPagination::Transaction`], `after` skips and `count` bounds the number of
+    /// matched _transactions_ rather than traces, so that all matching traces of a transaction are
+    /// always returned on the same page.
+    ///
+    /// The blocks are fetched and traced in a sliding window of
+    /// [`EthConfig::max_trace_filter_concurrency()`] blocks, so that only the blocks of the window
+    /// are held in memory rather than all blocks of the range.
+    pub async fn trace_filter_with_options(
+        &self,
+        filter: TraceFilter,
+        options: TraceFilterOptions,
+    ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
+        // We'll reuse the matcher across multiple blocks that are traced in parallel
+        let matcher = Arc::new(filter.matcher());
+        let pagination = options.pagination;
+        let options = Arc::new(options);
+        let numbers = self.trace_filter_block_numbers(&filter)?;
+        let TraceFilter { after, count, .. } = filter;
+        let allowlist = self.inner.address_allowlist.clone();
+
+        let mut block_traces = self.trace_filter_window(numbers, {
+            let matcher = matcher.clone();
+            let options = options.clone();
+            let allowlist = allowlist.clone();
+            move |tx_info, builder| {
+                let mut traces = builder.into_localized_transaction_traces(tx_info);
+                traces.retain(|trace| {
+                    matcher.matches(&trace.trace) &&
+                        options.matches(&trace.trace) &&
+                        is_allowed_trace(allowlist.as_deref(), &trace.trace)
+                });
+                Ok(traces)
+            }
+        });
+
+        let mut all_traces = Vec::new();
+        let mut include_rewards = self.inner.eth_config.include_reward_traces;
+        while let Some((block, traces)) = block_traces.next().await.transpose()? {
+            all_traces.extend(traces.into_iter().flatten());
+
+            // the reward traces of a block follow its transaction traces, unless disabled
+            if !include_rewards {
+                continue
+            }
+            if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
+                all_traces.extend(
+                    self.extract_reward_traces(
+                        block.header(),
+                        block.body().ommers(),
+                        base_block_reward,
+                    )
+                    .into_iter()
+                    .filter(|trace| {
+                        matcher.matches(&trace.trace) &&
+                            options.matches(&trace.trace) &&
+                            is_allowed_trace(allowlist.as_deref(), &trace.trace)
+                    }),
+                );
+            } else {
+                // no block reward, means we're past the Paris hardfork and don't expect any rewards
+                // because the blocks in ascending order
+                include_rewards = false;
+            }
+        }
+
+        options.order.sort(&mut all_traces);
+        Ok(pagination.paginate(all_traces, after, count))
+    }
+
+    /// Returns the distinct hashes of all transactions with a trace that matches the given filter,
+    /// in block order.
+    ///
+    /// This is a lightweight alternative to [`Self::trace_filter_with_options`] that doesn't
+    /// return the traces themselves. Reward traces don't belong to a transaction and are never
+    /// matched. `after` and `count` of the [`TraceFilter`] are applied to the transactions, the
+    /// [`TraceFilterOptions::pagination`] mode has no effect.
+    pub async fn trace_filter_hashes(
+        &self,
+        filter: TraceFilter,
+        options: TraceFilterOptions,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let matcher = Arc::new(filter.matcher());
+        let options = Arc::new(options);
+        let numbers = self.trace_filter_block_numbers(&filter)?;
+        let after = filter.after.unwrap_or(0) as usize;
+        let count = filter.count.map_or(usize::MAX, |count| count as usize);
+        let allowlist = self.inner.address_allowlist.clone();
+
+        let mut blocks = self.trace_filter_window(numbers, move |tx_info, builder| {
+            // the traces only need to be localized if they're returned
+            let matches = builder.into_transaction_traces().iter().any(|trace| {
+                matcher.matches(trace) &&
+                    options.matches(trace) &&
+                    is_allowed_trace(allowlist.as_deref(), trace)
+            });
+            Ok(tx_info.hash.filter(|_| matches))
+        });
+
+        let mut hashes = Vec::new();
+        while let Some((_, block_hashes)) = blocks.next().await.transpose()? {
+            hashes.extend(block_hashes.into_iter().flatten());
+            // the remaining blocks can't contribute to the page
+            if hashes.len() >= after.saturating_add(count) {
+                break
+            }
+        }
+        Ok(hashes.into_iter().skip(after).take(count).collect())
+    }
+
+    /// Returns the number of traces that match the given filter, without returning the traces
+    /// themselves.
+    ///
+    /// This matches the same traces as [`Self::trace_filter`], including reward traces if enabled
+    /// via [`EthConfig::include_reward_traces()`]. `after` and `count` of the [`TraceFilter`] are
+    /// applied to individual traces, i.e. this returns the length of the page that
+    /// [`Self::trace_filter`] would return. No blocks are traced if `count` is zero.
+    pub async fn trace_filter_count(&self, filter: TraceFilter) -> Result<u64, Eth::Error> {
+        let numbers = self.trace_filter_block_numbers(&filter)?;
+        if filter.count == Some(0) {
+            return Ok(0)
+        }
+        let matcher = Arc::new(filter.matcher());
+        let allowlist = self.inner.address_allowlist.clone();
+
+        let mut block_counts = self.trace_filter_window(numbers, {
+            let matcher = matcher.clone();
+            let allowlist = allowlist.clone();
+            move |_, builder| {
+                // the traces only need to be localized if they're returned
+                Ok(builder
+                    .into_transaction_traces()
+                    .iter()
+                    .filter(|trace| {
+                        matcher.matches(trace) && is_allowed_trace(allowlist.as_deref(), trace)
+                    })
+                    .count() as u64)
+            }
+        });
+
+        let mut total = 0;
+        let mut include_rewards = self.inner.eth_config.include_reward_traces;
+        while let Some((block, counts)) = block_counts.next().await.transpose()? {
+            total += counts.into_iter().sum::<u64>();
+
+            if !include_rewards {
+                continue
+            }
+            if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
+                total += self
+                    .extract_reward_traces(block.header(), block.body().ommers(), base_block_reward)
+                    .iter()
+                    .filter(|trace| {
+                        matcher.matches(&trace.trace) &&
+                            is_allowed_trace(allowlist.as_deref(), &trace.trace)
+                    })
+                    .count() as u64;
+            } else {
+                // no block reward, means we're past the Paris hardfork and don't expect any rewards
+                // because the blocks in ascending order
+                include_rewards = false;
+            }
+        }
+
+        Ok(paginated_count(total, filter.after, filter.count))
+    }
+
+    /// Returns a rough estimate of the number of traces [`Self::trace_filter`] would return for the
+    /// given filter, without tracing any blocks.
+    ///
+    /// The estimate is derived from the body indices of the blocks in the filter's range: every
+    /// transaction counts as a single trace, i.e. its top-level call, and the reward traces are
+    /// counted from the headers if enabled via [`EthConfig::include_reward_traces()`]. Only the
+    /// bodies of blocks with ommers are read. The `from` and `to` addresses
+    /// of the filter only narrow down the blocks if a [`TraceIndexProvider`] is registered, the
+    /// traces themselves aren't matched. `after` and `count` are applied like in
+    /// [`Self::trace_filter_count`].
+    ///
+    /// This is only meant to judge the order of magnitude of a query, e.g. to decide whether to
+    /// narrow down the range.
+    pub async fn trace_filter_estimate(&self, filter: TraceFilter) -> Result<u64, Eth::Error> {
+        let numbers = self.trace_filter_block_numbers(&filter)?;
+
+        let mut total = 0;
+        let mut include_rewards = self.inner.eth_config.include_reward_traces;
+        for number in numbers {
+            total += self
+                .provider()
+                .block_body_indices(number)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(number.into()))?
+                .tx_count();
+            if !include_rewards {
+                continue
+            }
+
+            let header = self
+                .provider()
+                .header_by_number(number)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+            if self.calculate_base_block_reward(&header)?.is_none() {
+                // no block reward, means we're past the Paris hardfork and don't expect any
+                // rewards because the blocks in ascending order
+                include_rewards = false;
+                continue
+            }
+            let ommers = if header.ommers_hash() == EMPTY_OMMER_ROOT_HASH {
+                0
+            } else {
+                self.provider()
+                    .block(number.into())
+                    .map_err(Eth::Error::from_eth_err)?
+                    .and_then(|block| block.body().ommers().map(|ommers| ommers.len()))
+                    .unwrap_or_default()
+            };
+            total += 1 + ommers as u64;
+        }
+
+        Ok(paginated_count(total, filter.after, filter.count))
+    }
+
+    /// Awaits the tracing futures of the blocks of a trace filter, see
+    /// [`try_join_all_limited`].
+    ///
+    /// At most [`EthConfig::max_trace_filter_concurrency()`] blocks are traced concurrently, in
+    /// addition to the tracing permit held by the request.
+    async fn try_join_filter_blocks<F, T>(&self, blocks: Vec<F>) -> Result<Vec<T>, Eth::Error>
+    where
+        F: Future<Output = Result<T, Eth::Error>>,
+    {
+        try_join_all_limited(blocks, self.inner.eth_config.max_trace_filter_concurrency).await
+    }
+
+    /// Fetches and traces the blocks with the given numbers of a trace filter, yielding each block
+    /// along with the results of `f` for its transactions, in block order.
+    ///
+    /// A block is only fetched once it enters the window of the next
+    /// [`EthConfig::max_trace_filter_concurrency()`] blocks to yield.
+    fn trace_filter_window<'a, F, R>(
+        &'a self,
+        numbers: Vec<BlockNumber>,
+        f: F,
+    ) -> impl Stream<Item = Result<(SharedRecoveredBlock<Eth::Provider>, Vec<R>), Eth::Error>> + 'a
+    where
+        F: Fn(TransactionInfo, ParityTraceBuilder) -> Result<R, Eth::Error>
+            + Clone
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
+        futures::stream::iter(numbers)
+            .map(move |number| {
+                let f = f.clone();
+                async move {
+                    let block = self.trace_filter_block(number)?;
+                    let results = self
+                        .eth_api()
+                        .trace_block_until(
+                            block.hash().into(),
+                            Some(block.clone()),
+                            None,
+                            TracingInspectorConfig::default_parity(),
+                            move |tx_info, ctx| f(tx_info, ctx.inspector.into_parity_builder()),
+                        )
+                        .await?
+                        .unwrap_or_default();
+                    Ok((block, results))
+                }
+            })
+            .buffered(self.inner.eth_config.max_trace_filter_concurrency.max(1))
+    }
+
+    /// Fetches the block with the given number of a trace filter, see [`canonical_block`].
+    fn trace_filter_block(
+        &self,
+        number: BlockNumber,
+    ) -> Result<SharedRecoveredBlock<Eth::Provider>, Eth::Error> {
+        canonical_block::<<Eth as RpcNodeCore>::Primitives, _>(
+            self.provider(),
+            number.into(),
+            CANONICAL_BLOCK_READ_ATTEMPTS,
+        )
+        .map_err(Eth::Error::from_eth_err)?
+        .map(Arc::new)
+        .ok_or_else(|| EthApiError::HeaderNotFound(number.into()).into())
+    }
+
+    /// Fetches the block with the given id to trace.
+    ///
+    /// The pending block is assembled on demand. Blocks requested by number or tag, and blocks
+    /// requested by hash that are required to be canonical, are read via [`canonical_block`] so
+    /// that a block that is replaced by a reorg while it's read isn't traced. Any other block
+    /// requested by hash is fetched via the cache, which also serves sidechain blocks.
+    async fn trace_block_target(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<SharedRecoveredBlock<Eth::Provider>>, Eth::Error> {
+        let id = match block_id {
+            BlockId::Hash(RpcBlockHash { block_hash, require_canonical: Some(true) }) => {
+                block_hash.into()
+            }
+            BlockId::Hash(RpcBlockHash { block_hash, .. }) => {
+                let block = self.eth_api().recovered_block(block_id).await?;
+                // only trace the block if it's the one that was asked for
+                return Ok(block.filter(|block| block.hash() == block_hash))
+            }
+            BlockId::Number(BlockNumberOrTag::Pending) => {
+                return self.eth_api().recovered_block(block_id).await
+            }
+            BlockId::Number(number) => {
+                let Some(number) = self
+                    .provider()
+                    .convert_block_number(number)
+                    .map_err(Eth::Error::from_eth_err)?
+                else {
+                    return Ok(None)
+                };
+                number.into()
+            }
+        };
+        Ok(canonical_block::<<Eth as RpcNodeCore>::Primitives, _>(
+            self.provider(),
+            id,
+            CANONICAL_BLOCK_READ_ATTEMPTS,
+        )
+        .map_err(Eth::Error::from_eth_err)?
+        .map(Arc::new))
+    }
+
+    /// Validates the block range of a trace filter and returns the numbers of all blocks in it
+    /// that need to be traced, in ascending order.
+    ///
+    /// If a [`TraceIndexProvider`] is registered and the filter restricts the `from` or `to`
+    /// addresses, only the blocks with traces involving these addresses are returned and the
+    /// number of these blocks, rather than the length of the range, is limited.
+    ///
+    /// If a [`TraceApiBuilder::address_allowlist`] is set, filters that don't restrict the
+    /// addresses to allowed ones are rejected.
+    fn trace_filter_block_numbers(
+        &self,
+        filter: &TraceFilter,
+    ) -> Result<Vec<BlockNumber>, Eth::Error> {
+        check_allowed_filter(self.inner.address_allowlist.as_deref(), filter)?;
+        let start = filter.from_block.unwrap_or(0);
+
+        let latest_block = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if start > latest_block {
+            // can't trace that range
+            return Err(EthApiError::HeaderNotFound(start.into()).into());
+        }
+        let end = filter.to_block.unwrap_or(latest_block);
+
+        if start > end {
+            return Err(EthApiError::InvalidParams(
+                "invalid parameters: fromBlock cannot be greater than toBlock".to_string(),
+            )
+            .into())
+        }
+
+        let trace_index = self.inner.trace_index.read().clone();
+        if let Some(trace_index) = trace_index {
+            if let Some(numbers) = indexed_trace_filter_blocks(
+                &*trace_index,
+                filter,
+                start..=end,
+                self.inner.eth_config.max_trace_filter_blocks,
+            )? {
+                return Ok(numbers)
+            }
+        }
+
+        // ensure that the range is not too large, since we need to trace all blocks in the range
+        check_trace_filter_range(start..=end, self.inner.eth_config.max_trace_filter_blocks)?;
+
+        // blocks past the latest block don't exist yet and are skipped
+        Ok((start..=end.min(latest_block)).collect())
+    }
+
+    /// Returns traces created at given block.
+    ///
+    /// For [`BlockId::pending()`] the pending block is assembled and its transactions are traced
+    /// on top of its parent's state.
+    ///
+    /// The block and uncle reward traces are only included if enabled via
+    /// [`EthConfig::include_reward_traces()`]. Fails once the traces of the block's transactions
+    /// exceed [`EthConfig::max_traces_per_response()`].
+    pub async fn trace_block(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<Vec<LocalizedTransactionTrace>>, Eth::Error> {
+        let frames = self.trace_block_with_options(block_id, TraceBlockOptions::default()).await?;
+        Ok(frames.map(|frames| frames.into_iter().map(|frame| frame.trace).collect()))
+    }
+
+    /// Returns the traces created at each block of the given inclusive range, like
+    /// [`Self::trace_block`], along with the block number, in block order.
+    ///
+    /// The range is limited to [`EthConfig::max_trace_filter_blocks()`] blocks, and at most
+    /// [`EthConfig::max_trace_filter_concurrency()`] blocks are traced concurrently. Returns
+    /// [`EthApiError::HeaderNotFound`] if a block of the range doesn't exist.
+    pub async fn trace_block_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<(BlockNumber, Vec<LocalizedTransactionTrace>)>, Eth::Error> {
+        if from > to {
+            return Err(EthApiError::InvalidParams(
+                "invalid parameters: fromBlock cannot be greater than toBlock".to_string(),
+            )
+            .into())
+        }
+        check_trace_filter_range(from..=to, self.inner.eth_config.max_trace_filter_blocks)?;
+
+        let blocks = (from..=to)
+            .map(|number| async move {
+                let traces = self
+                    .trace_block(number.into())
+                    .await?
+                    .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                Ok((number, traces))
+            })
+            .collect();
+        self.try_join_filter_blocks(blocks).await
+    }
+
+    /// Returns a stream of the traces created at the given block.
+    ///
+    /// This is the streaming variant of [`Self::trace_block`]: the traces of each transaction are
+    /// yielded as soon as the transaction is traced, in transaction order, followed by the reward
+    /// traces of the block. At most [`TRACE_BLOCK_STREAM_BUFFER`] traces are buffered in the
+    /// stream and tracing stops once the stream is dropped.
+    ///
+    /// Like the other tracing methods, the block is traced under a tracing permit, which is held
+    /// until all traces are yielded, and the stream yields [`EthApiError::ExecutionTimedOut`] once
+    /// [`EthConfig::trace_call_timeout`] expires.
+    ///
+    /// Yields a single [`EthApiError::HeaderNotFound`] error if the block doesn't exist.
+    pub fn trace_block_stream(
+        &self,
+        block_id: BlockId,
+    ) -> impl Stream<Item = Result<LocalizedTransactionTrace, Eth::Error>> + Send + 'static {
+        let (sender, receiver) = mpsc::channel(TRACE_BLOCK_STREAM_BUFFER);
+        let this = self.clone();
+        let driver = async move {
+            let _permit = this.acquire_trace_permit().await;
+            if let Err(err) =
+                this.with_trace_timeout(this.send_block_traces(block_id, &sender)).await
+            {
+                let _ = sender.send(Err(err)).await;
+            }
+        };
+        drive_receiver(receiver, driver)
+    }
+
+    /// Traces the given block and sends the traces of each transaction and the reward traces to
+    /// the given channel.
+    async fn send_block_traces(
+        &self,
+        block_id: BlockId,
+        sender: &mpsc::Sender<Result<LocalizedTransactionTrace, Eth::Error>>,
+    ) -> Result<(), Eth::Error> {
+        let block = self
+            .trace_block_target(block_id)
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        // the tracing task hands over the traces of each transaction without blocking, they're
+        // forwarded to the bounded stream buffer from here
+        let (tx_sender, mut tx_receiver) = mpsc::unbounded_channel();
+        let trace = self.eth_api().trace_block_with(
+            block_id,
+            Some(block.clone()),
+            TracingInspectorConfig::default_parity(),
+            move |tx_info, ctx| {
+                let traces =
+                    ctx.inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
+                // stop tracing the block if the stream was dropped
+                tx_sender.send(traces).map_err(|_| EthApiError::InternalEthError)?;
+                Ok(())
+            },
+        );
+        let forward = async move {
+            while let Some(traces) = tx_receiver.recv().await {
+                for trace in traces {
+                    if sender.send(Ok(trace)).await.is_err() {
+                        // dropping the receiver stops the tracing task
+                        return
+                    }
+                }
+            }
+        };
+        futures::join!(trace, forward).0?;
+
+        if !self.inner.eth_config.include_reward_traces {
+            return Ok(())
+        }
+        if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
+            for trace in
+                self.extract_reward_traces(block.header(), block.body().ommers(), base_block_reward)
+            {
+                if sender.send(Ok(trace)).await.is_err() {
+                    break
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns traces created at given block, configured by the given [`TraceBlockOptions`].
+    ///
+    /// Returns `None` for a block that doesn't exist or, if requested by hash, isn't canonical.
+    ///
+    /// With [`TraceBlockOptions::verify_against_receipts`], the success and gas used of each
+    /// traced transaction are checked against the block's stored receipts and a
+    /// [`TraceReceiptMismatch`] is returned as internal error on the first mismatch.
+    ///
+    /// With [`TraceBlockOptions::verify_state_root`], the block is executed on top of its parent's
+    /// state and the resulting state root is checked against the header's.
+    ///
+    /// With [`TraceBlockOptions::exec_index`], the traces of the block's transactions are numbered
+    /// in the order the inspector recorded them, see [`BlockTraceFrame::exec_index`].
+    pub async fn trace_block_with_options(
+        &self,
+        block_id: BlockId,
+        options: TraceBlockOptions,
+    ) -> Result<Option<Vec<BlockTraceFrame>>, Eth::Error> {
+        // resolve the block upfront so that the traces and the reward traces are created for the
+        // same block, the pending block is assembled on demand and could change in between
+        let Some(block) = self.trace_block_target(block_id).await? else { return Ok(None) };
+
+        let max_traces = self.inner.eth_config.max_traces_per_response;
+        let traced = AtomicUsize::new(0);
+        let Some(traces) = self
+            .eth_api()
+            .trace_block_with(
+                block_id,
+                Some(block.clone()),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, ctx| {
+                    // checked before building the traces of each transaction
+                    count_traces(&traced, ctx.inspector.traces().nodes().len(), max_traces)?;
+                    let outcome = TracedOutcome {
+                        success: ctx.result.is_success(),
+                        gas_used: ctx.result.gas_used(),
+                    };
+                    let traces = ctx
+                        .inspector
+                        .into_parity_builder()
+                        .into_localized_transaction_traces(tx_info);
+                    Ok((traces, outcome))
+                },
+            )
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        if options.verify_against_receipts {
+            let receipts = self
+                .provider()
+                .receipts_by_block(block.hash().into())
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+            let outcomes = traces.iter().map(|(_, outcome)| *outcome).collect::<Vec<_>>();
+            verify_against_receipts::<<Eth as RpcNodeCore>::Primitives>(&outcomes, &receipts)
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+        }
+        if options.verify_state_root {
+            let block = block.clone();
+            let evm_config = self.eth_api().evm_config().clone();
+            self.eth_api()
+                .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                    verify_state_root(&evm_config, state, &block).map_err(Eth::Error::from_eth_err)
+                })
+                .await?;
+        }
+
+        let mut frames =
+            block_trace_frames(traces.into_iter().map(|(traces, _)| traces), options.exec_index);
+
+        if self.inner.eth_config.include_reward_traces {
+            // the reward of the pending block is derived from the number of the pending header
+            if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
+                // reward traces aren't executed, so they're never numbered
+                frames.extend(
+                    self.extract_reward_traces(
+                        block.header(),
+                        block.body().ommers(),
+                        base_block_reward,
+                    )
+                    .into_iter()
+                    .map(|trace| BlockTraceFrame { trace, exec_index: None }),
+                );
+            }
+        }
+
+        Ok(Some(frames))
+    }
+
+    /// Replays all transactions in a block
+    pub async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> Result<Option<Vec<TraceResultsWithTransactionHash>>, Eth::Error> {
+        self.eth_api()
+            .trace_block_with(
+                block_id,
+                None,
+                TracingInspectorConfig::from_parity_config(&trace_types),
+                move |tx_info, ctx| {
+                    let mut full_trace = ctx
+                        .inspector
+                        .into_parity_builder()
+                        .into_trace_results(&ctx.result, &trace_types);
+
+                    // If statediffs were requested, populate them with the account balance and
+                    // nonce from pre-state
+                    if let Some(ref mut state_diff) = full_trace.state_diff {
+                        populate_state_diff(state_diff, &ctx.db, ctx.state.iter())
+                            .map_err(Eth::Error::from_eth_err)?;
+                    }
+
+                    let trace = TraceResultsWithTransactionHash {
+                        transaction_hash: tx_info.hash.expect("tx hash is set"),
+                        full_trace,
+                    };
+                    Ok(trace)
+                },
+            )
+            .await
+    }
+
+    /// Replays the transactions at the given indices of a block, like
+    /// [`Self::replay_block_transactions`].
+    ///
+    /// All transactions up to the highest selected index are executed on top of each other, so
+    /// that each selected transaction sees the state of the preceding ones, but only the results
+    /// of the selected transactions are returned, in transaction order. Returns
+    /// [`EthApiError::InvalidParams`] if no index is selected or an index is out of range.
+    pub async fn replay_block_transactions_selective(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+        selector: Vec<usize>,
+    ) -> Result<Option<Vec<TraceResultsWithTransactionHash>>, Eth::Error> {
+        let Some(block) = self.eth_api().recovered_block(block_id).await? else { return Ok(None) };
+        let selected = replay_selection(selector, block.body().transaction_count())?;
+        let highest_index = selected.last().map(|idx| *idx as u64);
+
+        let results = self
+            .eth_api()
+            .trace_block_until(
+                block_id,
+                Some(block),
+                highest_index,
+                TracingInspectorConfig::from_parity_config(&trace_types),
+                move |tx_info, ctx| {
+                    let idx = tx_info.index.expect("tx index is set") as usize;
+                    if !selected.contains(&idx) {
+                        return Ok(None)
+                    }
+                    let mut full_trace = ctx
+                        .inspector
+                        .into_parity_builder()
+                        .into_trace_results(&ctx.result, &trace_types);
+                    if let Some(ref mut state_diff) = full_trace.state_diff {
+                        populate_state_diff(state_diff, &ctx.db, ctx.state.iter())
+                            .map_err(Eth::Error::from_eth_err)?;
+                    }
+
+                    Ok(Some(TraceResultsWithTransactionHash {
+                        transaction_hash: tx_info.hash.expect("tx hash is set"),
+                        full_trace,
+                    }))
+                },
+            )
+            .await?;
+        Ok(results.map(|results| results.into_iter().flatten().collect()))
+    }
+
+    /// Returns the opcodes of all transactions in the given block.
+    ///
+    /// This is the same as [`Self::trace_transaction_opcode_gas`] but for all transactions in a
+    /// block.
+    pub async fn trace_block_opcode_gas(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<BlockOpcodeGas>, Eth::Error> {
+        let res = self
+            .eth_api()
+            .trace_block_inspector(
+                block_id,
+                None,
+                OpcodeGasInspector::default,
+                move |tx_info, ctx| {
+                    let trace = TransactionOpcodeGas {
+                        transaction_hash: tx_info.hash.expect("tx hash is set"),
+                        opcode_gas: ctx.inspector.opcode_gas_iter().collect(),
+                    };
+                    Ok(trace)
+                },
+            )
+            .await?;
+
+        let Some(transactions) = res else { return Ok(None) };
+
+        let Some(block) = self.eth_api().recovered_block(block_id).await? else { return Ok(None) };
+
+        Ok(Some(BlockOpcodeGas {
+            block_hash: block.hash(),
+            block_number: block.number(),
+            transactions,
+        }))
+    }
+}
+
+#[async_trait]
+impl<Eth> TraceApiServer for TraceApi<Eth>
+where
+    Eth: TraceExt + 'static,
+{
+    /// Executes the given call and returns a number of possible traces for it.
+    ///
+    /// Handler for `trace_call`
+    async fn trace_call(
+        &self,
+        call: TransactionRequest,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<TraceResults> {
+        let _permit = self.acquire_trace_permit().await;
+        let request =
+            TraceCallRequest { call, trace_types, block_id, state_overrides, block_overrides };
+        Ok(self.with_trace_timeout(Self::trace_call(self, request)).await.map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_callMany`
+    async fn trace_call_many(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<TraceResults>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_call_many(self, calls, block_id))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_rawTransaction`
+    async fn trace_raw_transaction(
+        &self,
+        data: Bytes,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<TraceResults> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_raw_transaction(
+                self,
+                data,
+                trace_types,
+                block_id,
+                block_overrides,
+            ))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_rawTransactionMany`
+    async fn trace_raw_transaction_many(
+        &self,
+        txs: Vec<Bytes>,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<TraceResults>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_raw_transaction_many(self, txs, trace_types, block_id))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_replayBlockTransactions`
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<Option<Vec<TraceResultsWithTransactionHash>>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::replay_block_transactions(self, block_id, trace_types))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_replayTransaction`
+    async fn replay_transaction(
+        &self,
+        transaction: B256,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<TraceResults> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::replay_transaction(self, transaction, trace_types))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_block`
+    async fn trace_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self.with_trace_timeout(Self::trace_block(self, block_id)).await.map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_blockRange`
+    async fn trace_block_range(
+        &self,
+        from_block: U64,
+        to_block: U64,
+    ) -> RpcResult<Vec<BlockTraces>> {
+        let _permit = self.acquire_trace_permit().await;
+        let blocks = self
+            .with_trace_timeout(Self::trace_block_range(self, from_block.to(), to_block.to()))
+            .await
+            .map_err(Into::into)?;
+        Ok(blocks
+            .into_iter()
+            .map(|(block_number, traces)| BlockTraces { block_number, traces })
+            .collect())
+    }
+
+    /// Handler for `trace_filter`
+    ///
+    /// This is similar to `eth_getLogs` but for traces.
+    ///
+    /// # Limitations
+    /// This currently requires block filter fields, since reth does not have address indices yet.
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        Ok(self.with_trace_timeout(Self::trace_filter(self, filter)).await.map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_filterCount`
+    async fn trace_filter_count(&self, filter: TraceFilter) -> RpcResult<U64> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_filter_count(self, filter))
+            .await
+            .map(U64::from)
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_filterHashes`
+    async fn trace_filter_hashes(&self, filter: TraceFilter) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_filter_hashes(self, filter, Default::default()))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Returns transaction trace at given index.
+    /// Handler for `trace_get`
+    async fn trace_get(
+        &self,
+        hash: B256,
+        indices: Vec<Index>,
+    ) -> RpcResult<Option<LocalizedTransactionTrace>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_get(
+                self,
+                hash,
+                indices.into_iter().map(Into::into).collect(),
+            ))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_getMany`
+    async fn trace_get_many(
+        &self,
+        hash: B256,
+        indices: Vec<Index>,
+    ) -> RpcResult<Vec<Option<LocalizedTransactionTrace>>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_get_many(
+                self,
+                hash,
+                indices.into_iter().map(Into::into).collect(),
+            ))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_transaction`
+    async fn trace_transaction(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_transaction(self, hash))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_transactionOpcodeGas`
+    async fn trace_transaction_opcode_gas(
+        &self,
+        tx_hash: B256,
+    ) -> RpcResult<Option<TransactionOpcodeGas>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_transaction_opcode_gas(self, tx_hash))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_blockOpcodeGas`
+    async fn trace_block_opcode_gas(&self, block_id: BlockId) -> RpcResult<Option<BlockOpcodeGas>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(self
+            .with_trace_timeout(Self::trace_block_opcode_gas(self, block_id))
+            .await
+            .map_err(Into::into)?)
+    }
+
+    /// Handler for `trace_capabilities`
+    async fn trace_capabilities(&self) -> RpcResult<TraceCapabilities> {
+        Ok(Self::trace_capabilities(self))
+    }
+}
+
+impl<Eth> std::fmt::Debug for TraceApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TraceApi").finish_non_exhaustive()
+    }
+}
+impl<Eth> Clone for TraceApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct TraceApiInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    // restrict the number of concurrent calls to `trace_*`
+    blocking_task_guard: BlockingTaskGuard,
+    // eth config settings
+    eth_config: EthConfig,
+    // custom inspector registered via `TraceApi::register_custom_inspector`
+    custom_inspector: RwLock<Option<CustomTraceInspectorFactory>>,
+    // trace index registered via `TraceApi::register_trace_index`
+    trace_index: RwLock<Option<Arc<dyn TraceIndexProvider>>>,
+    // cache of `trace_transaction` results, if enabled via `EthConfig`
+    transaction_traces: Option<TransactionTraceCache>,
+    // block rewards registered via `TraceApi::register_block_rewards`
+    block_rewards: RwLock<Option<Arc<dyn BlockRewardProvider>>>,
+    // addresses tracing is restricted to, set via `TraceApiBuilder::address_allowlist`
+    address_allowlist: Option<Arc<HashSet<Address>>>,
+}
+
+/// Additional options for [`TraceApi::trace_call_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceCallOptions {
+    /// Whether to report the outcome of each authorization if the call carries an EIP-7702
+    /// authorization list.
+    pub authorizations: bool,
+    /// Whether to count how many times each opcode was executed.
+    pub opcode_counts: bool,
+    /// Whether to run the [`CustomTraceInspector`] registered via
+    /// [`TraceApi::register_custom_inspector`].
+    ///
+    /// To only run the custom inspector, request no trace types.
+    pub custom_inspector: bool,
+    /// Simulates the call as if it was executed on the chain with the given id instead of the
+    /// node's chain id, affecting e.g. the `CHAINID` opcode and EIP-7702 authorizations.
+    ///
+    /// This produces hypothetical results. The call is simulated rather than signed, so there's no
+    /// signature of the call that is validated against the chain id.
+    pub chain_id_override: Option<u64>,
+    /// Whether to report the balance and nonce of the caller before the call is executed.
+    ///
+    /// This is the state at the traced block with the state overrides of the request applied.
+    pub pre_state: bool,
+    /// Whether to also report the balance and nonce of the recipient before the call is executed.
+    ///
+    /// Only has an effect together with [`Self::pre_state`].
+    pub pre_state_recipient: bool,
+    /// Whether to return the logs emitted by the call.
+    pub logs: bool,
+    /// Whether to decode well-known token events, see [`DecodedEvent`].
+    ///
+    /// Only has an effect together with [`Self::logs`]. The raw logs are always returned.
+    pub decode_events: bool,
+    /// Whether to reject calls with a gas limit above the block gas limit.
+    ///
+    /// By default such calls are traced, even though they couldn't be included in the block.
+    pub enforce_block_gas_limit: bool,
+    /// Whether to split the gas of the call into intrinsic and execution gas.
+    pub gas_split: bool,
+    /// Whether to report the gas used by the call and whether its gas limit was sufficient.
+    pub gas_info: bool,
+    /// The config of the [`TracingInspector`], used verbatim instead of the config derived from
+    /// the requested trace types.
+    ///
+    /// This allows recording more than the trace types need, e.g. memory and stack snapshots, or
+    /// less, e.g. no steps at all. Traces that need data the config doesn't record are returned
+    /// incomplete, e.g. a `vmTrace` without steps.
+    pub inspector_config: Option<TracingInspectorConfig>,
+    /// Whether to return the changed storage slots of the call as a [`StorageDiff`].
+    ///
+    /// This is derived from the same execution as a requested [`TraceType::StateDiff`], but
+    /// omits balance, nonce and code changes. To only return the storage changes, request no
+    /// trace types.
+    pub storage_diff: bool,
+    /// Whether to report the EIP-7702 delegate of each call into an account with a delegation
+    /// designator.
+    ///
+    /// Only has an effect if [`TraceType::Trace`] is requested, the delegations reference the
+    /// calls by their trace address.
+    pub delegations: bool,
+    /// Whether to name the well-known precompiles called by the call, see [`Precompile`].
+    ///
+    /// Only has an effect if [`TraceType::Trace`] is requested. The traces are returned
+    /// unchanged, the precompile calls reference them by their trace address.
+    pub precompiles: bool,
+}
+
+impl TraceCallOptions {
+    /// Enables reporting the outcome of each EIP-7702 authorization.
+    pub const fn with_authorizations(mut self) -> Self {
+        self.authorizations = true;
+        self
+    }
+
+    /// Enables counting the executed opcodes.
+    pub const fn with_opcode_counts(mut self) -> Self {
+        self.opcode_counts = true;
+        self
+    }
+
+    /// Enables running the registered [`CustomTraceInspector`].
+    pub const fn with_custom_inspector(mut self) -> Self {
+        self.custom_inspector = true;
+        self
+    }
+
+    /// Simulates the call on the chain with the given id.
+    pub const fn with_chain_id_override(mut self, chain_id: u64) -> Self {
+        self.chain_id_override = Some(chain_id);
+        self
+    }
+
+    /// Enables reporting the caller's state before the call, and the recipient's if
+    /// `include_recipient` is set.
+    pub const fn with_pre_state(mut self, include_recipient: bool) -> Self {
+        self.pre_state = true;
+        self.pre_state_recipient = include_recipient;
+        self
+    }
+
+    /// Enables returning the logs of the call, with the well-known events decoded if
+    /// `decode_events` is set.
+    pub const fn with_logs(mut self, decode_events: bool) -> Self {
+        self.logs = true;
+        self.decode_events = decode_events;
+        self
+    }
+
+    /// Enables rejecting calls with a gas limit above the block gas limit.
+    pub const fn with_enforce_block_gas_limit(mut self) -> Self {
+        self.enforce_block_gas_limit = true;
+        self
+    }
+
+    /// Enables splitting the gas of the call into intrinsic and execution gas.
+    pub const fn with_gas_split(mut self) -> Self {
+        self.gas_split = true;
+        self
+    }
+
+    /// Enables reporting the gas used by the call and whether it ran out of gas.
+    pub const fn with_gas_info(mut self) -> Self {
+        self.gas_info = true;
+        self
+    }
+
+    /// Sets the [`TracingInspectorConfig`] that overrides the config derived from the trace types.
+    pub const fn with_inspector_config(mut self, config: TracingInspectorConfig) -> Self {
+        self.inspector_config = Some(config);
+        self
+    }
+
+    /// Enables returning the changed storage slots of the call.
+    pub const fn with_storage_diff(mut self) -> Self {
+        self.storage_diff = true;
+        self
+    }
+
+    /// Enables reporting the EIP-7702 delegates of the calls.
+    pub const fn with_delegations(mut self) -> Self {
+        self.delegations = true;
+        self
+    }
+
+    /// Enables naming the called precompiles.
+    pub const fn with_precompiles(mut self) -> Self {
+        self.precompiles = true;
+        self
+    }
+
+    /// Returns the [`TracingInspectorConfig`] for the given trace types, which is the
+    /// [`Self::inspector_config`] override if set.
+    fn tracing_config(&self, trace_types: &HashSet<TraceType>) -> TracingInspectorConfig {
+        self.inspector_config
+            .unwrap_or_else(|| TracingInspectorConfig::from_parity_config(trace_types))
+    }
+}
+
+/// The result of [`TraceApi::trace_call_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallResults {
+    /// The requested traces.
+    #[serde(flatten)]
+    pub results: TraceResults,
+    /// The outcome of each authorization of the call's EIP-7702 authorization list.
+    ///
+    /// Only set if requested via [`TraceCallOptions::authorizations`] and the call carries an
+    /// authorization list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorizations: Option<Vec<AuthorizationOutcome>>,
+    /// The number of times each opcode was executed, keyed by opcode name when serialized.
+    ///
+    /// Only set if requested via [`TraceCallOptions::opcode_counts`].
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opcode_counts_serde")]
+    pub opcode_counts: Option<HashMap<OpCode, u64>>,
+    /// The result of the registered [`CustomTraceInspector`].
+    ///
+    /// Only set if requested via [`TraceCallOptions::custom_inspector`] and an inspector is
+    /// registered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom: Option<serde_json::Value>,
+    /// The state of the caller, followed by the recipient if requested, before the call is
+    /// executed.
+    ///
+    /// Only set if requested via [`TraceCallOptions::pre_state`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_state: Option<Vec<AccountSnapshot>>,
+    /// The logs emitted by the call, empty if the call failed.
+    ///
+    /// Only set if requested via [`TraceCallOptions::logs`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<TraceCallLog>>,
+    /// The gas of the call, split into intrinsic and execution gas.
+    ///
+    /// Only set if requested via [`TraceCallOptions::gas_split`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_split: Option<GasSplit>,
+    /// The gas used by the call and whether its gas limit was sufficient.
+    ///
+    /// Only set if requested via [`TraceCallOptions::gas_info`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_info: Option<GasInfo>,
+    /// The changed storage slots of the call.
+    ///
+    /// Only set if requested via [`TraceCallOptions::storage_diff`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_diff: Option<StorageDiff>,
+    /// The calls into accounts that delegate their code via EIP-7702, along with the delegate.
+    ///
+    /// Only set if requested via [`TraceCallOptions::delegations`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegations: Option<Vec<CallDelegation>>,
+    /// The calls to well-known precompiles.
+    ///
+    /// Only set if requested via [`TraceCallOptions::precompiles`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precompiles: Option<Vec<PrecompileCall>>,
+}
+
+/// Additional options for [`TraceApi::trace_call_many_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceCallManyOptions {
+    /// Accounts whose balance and nonce are recorded after each call.
+    ///
+    /// With [`Self::independent`], the snapshot after a call only includes the state changes of
+    /// that call.
+    pub snapshot_accounts: Vec<Address>,
+    /// Gas limit overrides, parallel to the calls.
+    ///
+    /// An override takes precedence over the gas of the call's request. Calls without an entry or
+    /// with `None` use the gas of the request as usual.
+    pub gas_limits: Vec<Option<u64>>,
+    /// Whether each call is executed on top of the block state, rather than on top of the state
+    /// changes of the previous calls.
+    pub independent: bool,
+}
+
+impl TraceCallManyOptions {
+    /// Sets the accounts to record after each call.
+    pub fn with_snapshot_accounts(mut self, snapshot_accounts: Vec<Address>) -> Self {
+        self.snapshot_accounts = snapshot_accounts;
+        self
+    }
+
+    /// Sets the gas limit overrides of the calls.
+    pub fn with_gas_limits(mut self, gas_limits: Vec<Option<u64>>) -> Self {
+        self.gas_limits = gas_limits;
+        self
+    }
+
+    /// Enables executing each call independently of the previous calls.
+    pub const fn with_independent(mut self) -> Self {
+        self.independent = true;
+        self
+    }
+
+    /// Returns the gas limit override of the call at the given index, if any.
+    pub fn gas_limit(&self, idx: usize) -> Option<u64> {
+        self.gas_limits.get(idx).copied().flatten()
+    }
+}
+
+/// The result of [`TraceApi::trace_call_many_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallManyResults {
+    /// The traces of each call.
+    pub results: Vec<TraceResults>,
+    /// The snapshots of the requested accounts after each call, parallel to
+    /// [`Self::results`].
+    ///
+    /// Empty if no accounts were requested via [`TraceCallManyOptions::snapshot_accounts`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<Vec<AccountSnapshot>>,
+}
+
+/// (De)serializes opcode counts as a map from opcode name to count.
+mod opcode_counts_serde {
+    use alloy_primitives::map::HashMap;
+    use revm::bytecode::opcode::OpCode;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        counts: &Option<HashMap<OpCode, u64>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match counts {
+            Some(counts) => serializer
+                .collect_map(counts.iter().map(|(opcode, count)| (opcode.as_str(), count))),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<HashMap<OpCode, u64>>, D::Error> {
+        let Some(counts) = Option::<HashMap<String, u64>>::deserialize(deserializer)? else {
+            return Ok(None)
+        };
+        counts
+            .into_iter()
+            .map(|(name, count)| {
+                (0..=u8::MAX)
+                    .filter_map(OpCode::new)
+                    .find(|opcode| opcode.as_str() == name)
+                    .map(|opcode| (opcode, count))
+                    .ok_or_else(|| D::Error::custom(format!("unknown opcode {name}")))
+            })
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
+}
+
+/// Additional options for [`TraceApi::trace_block_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceBlockOptions {
+    /// Whether to check the traced outcome of each transaction against its stored receipt.
+    pub verify_against_receipts: bool,
+    /// Whether to check that executing the block results in the state root of its header.
+    ///
+    /// This requires a full state root computation and is expensive.
+    pub verify_state_root: bool,
+    /// Whether to number the traces of the block's transactions in execution order, see
+    /// [`BlockTraceFrame::exec_index`].
+    pub exec_index: bool,
+}
+
+impl TraceBlockOptions {
+    /// Enables checking the traced transactions against the block's receipts.
+    pub const fn with_verify_against_receipts(mut self) -> Self {
+        self.verify_against_receipts = true;
+        self
+    }
+
+    /// Enables checking the post-state root of the block against its header.
+    pub const fn with_verify_state_root(mut self) -> Self {
+        self.verify_state_root = true;
+        self
+    }
+
+    /// Enables numbering the traces in execution order.
+    pub const fn with_exec_index(mut self) -> Self {
+        self.exec_index = true;
+        self
+    }
+}
+
+/// A trace of the result of [`TraceApi::trace_block_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTraceFrame {
+    /// The trace.
+    #[serde(flatten)]
+    pub trace: LocalizedTransactionTrace,
+    /// The position of the trace in the execution of the whole block, starting at zero.
+    ///
+    /// The traces of a transaction are numbered in the order the inspector recorded them, which
+    /// is the order in which their frames were entered, and the numbering continues across the
+    /// transactions of the block.
+    ///
+    /// Only set if requested via [`TraceBlockOptions::exec_index`] and the trace isn't a reward
+    /// trace.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub exec_index: Option<u64>,
+}
+
+/// Additional options for [`TraceApi::trace_transaction_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceTransactionOptions {
+    /// Whether to annotate each frame with the gas that was available when it was entered.
+    pub gas_at_entry: bool,
+    /// Whether to annotate each call to a well-known precompile with the [`Precompile`].
+    pub precompiles: bool,
+}
+
+impl TraceTransactionOptions {
+    /// Enables annotating each frame with the gas available at entry.
+    pub const fn with_gas_at_entry(mut self) -> Self {
+        self.gas_at_entry = true;
+        self
+    }
+
+    /// Enables annotating the calls to precompiles.
+    pub const fn with_precompiles(mut self) -> Self {
+        self.precompiles = true;
+        self
+    }
+}
+
+/// A frame of the result of [`TraceApi::trace_transaction_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTraceFrame {
+    /// The trace of the frame.
+    #[serde(flatten)]
+    pub trace: LocalizedTransactionTrace,
+    /// The gas that was available when the frame was entered, i.e. the gas forwarded by the
+    /// parent frame after the 63/64 rule has been applied.
+    ///
+    /// Only set if requested via [`TraceTransactionOptions::gas_at_entry`] and the frame is a
+    /// call or create.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas_at_entry: Option<u64>,
+    /// The called precompile.
+    ///
+    /// Only set if requested via [`TraceTransactionOptions::precompiles`] and the frame is a call
+    /// to a well-known precompile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precompile: Option<Precompile>,
+}
+
+/// The result of [`TraceApi::trace_transaction_with_receipt`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTracesWithReceipt {
+    /// All traces of the transaction.
+    pub traces: Vec<LocalizedTransactionTrace>,
+    /// The gas used by the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Whether the transaction succeeded.
+    pub status: bool,
+    /// The output of the transaction, i.e. the return data of the top-level call or the revert
+    /// data.
+    pub output: Bytes,
+}
+
+/// Additional options for [`TraceApi::trace_filter_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceFilterOptions {
+    /// How the `after` and `count` fields of the [`TraceFilter`] are applied.
+    pub pagination: TraceFilterPagination,
+    /// If non-empty, only traces that created a contract at one of these addresses match, in
+    /// addition to the [`TraceFilter`].
+    ///
+    /// This only matches `CREATE` and `CREATE2` traces, all other traces are excluded.
+    pub created_addresses: HashSet<Address>,
+    /// If non-empty, only traces with an action of one of these kinds match, in addition to the
+    /// [`TraceFilter`].
+    pub action_kinds: HashSet<TraceActionKind>,
+    /// The order of the matched traces.
+    pub order: TraceFilterOrder,
+}
+
+impl TraceFilterOptions {
+    /// Sets the [`TraceFilterPagination`] mode.
+    pub const fn with_pagination(mut self, pagination: TraceFilterPagination) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// Sets the addresses of created contracts to match.
+    pub fn with_created_addresses(mut self, created_addresses: HashSet<Address>) -> Self {
+        self.created_addresses = created_addresses;
+        self
+    }
+
+    /// Sets the kinds of trace actions to match.
+    pub fn with_action_kinds(mut self, action_kinds: HashSet<TraceActionKind>) -> Self {
+        self.action_kinds = action_kinds;
+        self
+    }
+
+    /// Sets the [`TraceFilterOrder`] of the matched traces.
+    pub const fn with_order(mut self, order: TraceFilterOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns `true` if the trace matches the created addresses and action kinds of the options.
+    fn matches(&self, trace: &TransactionTrace) -> bool {
+        matches_created_address(&self.created_addresses, trace) &&
+            (self.action_kinds.is_empty() ||
+                self.action_kinds.contains(&TraceActionKind::of(&trace.action)))
+    }
+}
+
+/// The kind of the action of a trace, see [`TraceFilterOptions::action_kinds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceActionKind {
+    /// A `CALL`, including the top level call of a transaction.
+    Call,
+    /// A `CALLCODE`.
+    CallCode,
+    /// A `DELEGATECALL`.
+    DelegateCall,
+    /// A `STATICCALL`.
+    StaticCall,
+    /// A contract creation, via `CREATE`, `CREATE2` or a contract creation transaction.
+    Create,
+    /// A `SELFDESTRUCT`.
+    Selfdestruct,
+    /// A block or uncle reward.
+    Reward,
+}
+
+impl TraceActionKind {
+    /// Returns the kind of the given action.
+    pub const fn of(action: &Action) -> Self {
+        match action {
+            Action::Call(call) => match call.call_type {
+                CallType::CallCode => Self::CallCode,
+                CallType::DelegateCall => Self::DelegateCall,
+                CallType::StaticCall => Self::StaticCall,
+                _ => Self::Call,
+            },
+            Action::Create(_) => Self::Create,
+            Action::Selfdestruct(_) => Self::Selfdestruct,
+            Action::Reward(_) => Self::Reward,
+        }
+    }
+}
+
+/// The order of the traces returned by [`TraceApi::trace_filter_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceFilterOrder {
+    /// Traces are ordered by block number, then by transaction index and trace address, with the
+    /// reward traces of a block following its transaction traces.
+    #[default]
+    Block,
+    /// Traces are ordered by gas used in descending order, ties keep the [`Self::Block`] order.
+    ///
+    /// Selfdestruct and reward traces and traces of failed frames without an output have no gas
+    /// used and come last. The traces of a transaction are generally not adjacent, so with
+    /// [`TraceFilterPagination::Transaction`] each run of adjacent traces of the same transaction
+    /// counts as a separate entry.
+    GasUsed,
+}
+
+impl TraceFilterOrder {
+    /// Sorts the given traces, which are in [`Self::Block`] order, into this order.
+    fn sort(self, traces: &mut [LocalizedTransactionTrace]) {
+        match self {
+            Self::Block => {}
+            Self::GasUsed => traces.sort_by_key(|trace| std::cmp::Reverse(gas_used(&trace.trace))),
+        }
+    }
+}
+
+/// Determines how the `after` and `count` fields of a [`TraceFilter`] are applied to the matched
+/// traces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceFilterPagination {
+    /// `after` and `count` are applied to individual traces.
+    ///
+    /// This is the default and matches the behaviour of Parity and Erigon, a page can end in the
+    /// middle of a transaction's traces.
+    #[default]
+    Trace,
+    /// `after` and `count` are applied to whole transactions.
+    ///
+    /// All matching traces of a transaction are always part of the same page. Reward traces don't
+    /// belong to a transaction and are each counted as a separate entry.
+    Transaction,
+}
+
+impl TraceFilterPagination {
+    /// Skips the first `after` entries and returns at most `count` entries of the given traces.
+    ///
+    /// If `after` is greater than or equal to the number of entries, this returns an empty array.
+    fn paginate(
+        self,
+        mut traces: Vec<LocalizedTransactionTrace>,
+        after: Option<u64>,
+        count: Option<u64>,
+    ) -> Vec<LocalizedTransactionTrace> {
+        let after = after.unwrap_or_default() as usize;
+        let count = count.map(|count| count as usize).unwrap_or(usize::MAX);
+
+        let (start, end) = match self {
+            Self::Trace => (after, after.saturating_add(count)),
+            Self::Transaction => {
+                // traces are ordered by block and transaction, so the traces of a transaction are
+                // always adjacent
+                let mut starts = traces.iter().enumerate().filter_map(|(idx, trace)| {
+                    let is_new_entry = idx == 0 ||
+                        trace.transaction_hash.is_none() ||
+                        trace.transaction_hash != traces[idx - 1].transaction_hash;
+                    is_new_entry.then_some(idx)
+                });
+                let Some(start) = starts.nth(after) else { return Vec::new() };
+                let end = match count.checked_sub(1) {
+                    Some(remaining) => starts.nth(remaining).unwrap_or(traces.len()),
+                    None => start,
+                };
+                (start, end)
+            }
+        };
+
+        if start >= traces.len() {
+            return Vec::new()
+        }
+        traces.truncate(end);
+        traces.drain(..start);
+        traces
+    }
+}
+
+/// Returns the gas that was available when the frame of the given trace was entered.
+///
+/// This is the gas limit the tracer recorded for the frame, which is reported as the gas of call
+/// and create actions. Selfdestructs and rewards aren't frames and have no gas.
+const fn gas_at_entry(trace: &TransactionTrace) -> Option<u64> {
+    match &trace.action {
+        Action::Call(call) => Some(call.gas),
+        Action::Create(create) => Some(create.gas),
+        Action::Selfdestruct(_) | Action::Reward(_) => None,
+    }
+}
+
+/// Returns the gas used by the frame of the given trace, or `0` if the trace has no output.
+const fn gas_used(trace: &TransactionTrace) -> u64 {
+    match &trace.result {
+        Some(TraceOutput::Call(output)) => output.gas_used,
+        Some(TraceOutput::Create(output)) => output.gas_used,
+        None => 0,
+    }
+}
+
+/// Returns an error if an address allowlist is given and the filter doesn't restrict the `from` or
+/// `to` addresses, or includes an address that isn't allowed.
+fn check_allowed_filter(
+    allowlist: Option<&HashSet<Address>>,
+    filter: &TraceFilter,
+) -> Result<(), EthApiError> {
+    let Some(allowlist) = allowlist else { return Ok(()) };
+    if filter.from_address.is_empty() && filter.to_address.is_empty() {
+        return Err(EthApiError::InvalidParams(
+            "trace_filter must be restricted to the allowed addresses via fromAddress or toAddress"
+                .to_string(),
+        ))
+    }
+    if let Some(address) =
+        filter.from_address.iter().chain(&filter.to_address).find(|a| !allowlist.contains(*a))
+    {
+        return Err(EthApiError::InvalidParams(format!(
+            "address {address} is not allowed to be traced"
+        )))
+    }
+    Ok(())
+}
+
+/// Returns an error if an address allowlist is given and the call isn't a call to an allowed
+/// address.
+fn check_allowed_call_target(
+    allowlist: Option<&HashSet<Address>>,
+    to: Option<TxKind>,
+) -> Result<(), EthApiError> {
+    let Some(allowlist) = allowlist else { return Ok(()) };
+    match to {
+        Some(TxKind::Call(to)) if allowlist.contains(&to) => Ok(()),
+        Some(TxKind::Call(to)) => {
+            Err(EthApiError::InvalidParams(format!("address {to} is not allowed to be traced")))
+        }
+        _ => Err(EthApiError::InvalidParams(
+            "contract creations are not allowed to be traced".to_string(),
+        )),
+    }
+}
+
+/// Returns `true` if no address allowlist is given or the trace involves an allowed address.
+fn is_allowed_trace(allowlist: Option<&HashSet<Address>>, trace: &TransactionTrace) -> bool {
+    let Some(allowlist) = allowlist else { return true };
+    let created = trace.result.as_ref().and_then(|res| res.created_contract());
+    let addresses = match &trace.action {
+        Action::Call(call) => [Some(call.from), Some(call.to)],
+        Action::Create(create) => [Some(create.from), created],
+        Action::Selfdestruct(selfdestruct) => {
+            [Some(selfdestruct.address), Some(selfdestruct.refund_address)]
+        }
+        Action::Reward(reward) => [Some(reward.author), None],
+    };
+    addresses.into_iter().flatten().any(|address| allowlist.contains(&address))
+}
+
+/// Returns `true` if no created addresses are given or the trace created a contract at one of the
+/// given addresses.
+fn matches_created_address(created_addresses: &HashSet<Address>, trace: &TransactionTrace) -> bool {
+    created_addresses.is_empty() ||
+        trace
+            .result
+            .as_ref()
+            .and_then(|res| res.created_contract())
+            .is_some_and(|address| created_addresses.contains(&address))
+}
+
+/// Returns a stream of the items received by the given receiver, while driving the given future
+/// that sends them.
+///
+/// The stream ends once the future has completed and all items have been received.
+fn drive_receiver<T>(
+    receiver: mpsc::Receiver<T>,
+    driver: impl Future<Output = ()>,
+) -> impl Stream<Item = T> {
+    futures::stream::select(
+        ReceiverStream::new(receiver),
+        futures::stream::once(driver).filter_map(|()| futures::future::ready(None)),
+    )
+}
+
+/// Returns the blocks in the given range with traces involving the `from` or `to` addresses of the
+/// filter according to the given index, or `None` if the filter doesn't restrict any addresses.
+///
+/// Returns an error if there are more than `max_blocks` such blocks.
+fn indexed_trace_filter_blocks(
+    trace_index: &dyn TraceIndexProvider,
+    filter: &TraceFilter,
+    range: RangeInclusive<BlockNumber>,
+    max_blocks: u64,
+) -> Result<Option<Vec<BlockNumber>>, EthApiError> {
+    if filter.from_address.is_empty() && filter.to_address.is_empty() {
+        return Ok(None)
+    }
+
+    let numbers =
+        trace_index.trace_block_numbers(range, &filter.from_address, &filter.to_address)?;
+    if numbers.len() as u64 > max_blocks {
+        return Err(EthApiError::InvalidParams(format!(
+            "Too many blocks with matching addresses; currently limited to {max_blocks} blocks"
+        )))
+    }
+    Ok(Some(numbers))
+}
+
+/// Ensures that the distance between the start and end of the given range doesn't exceed
+/// `max_blocks`.
+fn check_trace_filter_range(
+    range: RangeInclusive<BlockNumber>,
+    max_blocks: u64,
+) -> Result<(), EthApiError> {
+    let distance = range.end().saturating_sub(*range.start());
+    if distance > max_blocks {
+        let requested = distance.saturating_add(1);
+        return Err(EthApiError::InvalidParams(format!(
+            "Block range too large: {requested} blocks requested, limited to {max_blocks} blocks"
+        )))
+    }
+    Ok(())
+}
+
+/// Adds the `count` traces of a transaction to the number of `traced` traces of a response and
+/// checks that the total doesn't exceed `max_traces`, if set.
+fn count_traces(
+    traced: &AtomicUsize,
+    count: usize,
+    max_traces: Option<usize>,
+) -> Result<(), EthApiError> {
+    check_trace_limit(traced.fetch_add(count, Ordering::Relaxed) + count, max_traces)
+}
+
+/// Returns an error if the `count` traces of a response exceed `max_traces`, if set.
+fn check_trace_limit(count: usize, max_traces: Option<usize>) -> Result<(), EthApiError> {
+    if let Some(max_traces) = max_traces.filter(|max_traces| count > *max_traces) {
+        return Err(EthApiError::InvalidParams(format!(
+            "Too many traces: response exceeds the limit of {max_traces} traces"
+        )))
+    }
+    Ok(())
+}
+
+/// Returns an error if `tx_index` isn't a position a transaction can be inserted at in a block with
+/// `tx_count` transactions.
+fn check_tx_position(tx_index: usize, tx_count: usize) -> Result<(), EthApiError> {
+    if tx_index > tx_count {
+        return Err(EthApiError::InvalidParams(format!(
+            "transaction index {tx_index} out of range, block has {tx_count} transactions"
+        )))
+    }
+    Ok(())
+}
+
+/// Returns the distinct transaction indices of a block with `tx_count` transactions selected by
+/// the given `selector`, in ascending order.
+fn replay_selection(selector: Vec<usize>, tx_count: usize) -> Result<BTreeSet<usize>, EthApiError> {
+    if selector.is_empty() {
+        return Err(EthApiError::InvalidParams("no transactions selected".to_string()))
+    }
+    if let Some(idx) = selector.iter().find(|idx| **idx >= tx_count) {
+        return Err(EthApiError::InvalidParams(format!(
+            "transaction index {idx} out of range, block has {tx_count} transactions"
+        )))
+    }
+    Ok(selector.into_iter().collect())
+}
+
+/// Wraps the traces of the transactions of a block, in block order, into [`BlockTraceFrame`]s,
+/// numbered consecutively if `exec_index` is set.
+fn block_trace_frames(
+    traces: impl IntoIterator<Item = Vec<LocalizedTransactionTrace>>,
+    exec_index: bool,
+) -> Vec<BlockTraceFrame> {
+    traces
+        .into_iter()
+        .flatten()
+        .zip(0u64..)
+        .map(|(trace, index)| BlockTraceFrame { trace, exec_index: exec_index.then_some(index) })
+        .collect()
+}
+
+/// Checks that a batch of `calls` calls doesn't exceed the configured maximum number of calls, if
+/// set.
+fn check_call_many_batch(calls: usize, max_calls: Option<usize>) -> Result<(), EthApiError> {
+    if let Some(max_calls) = max_calls.filter(|max_calls| calls > *max_calls) {
+        return Err(EthApiError::InvalidParams(format!(
+            "Too many calls: {calls} calls requested, limited to {max_calls} calls"
+        )))
+    }
+    Ok(())
+}
+
+/// Calculates the base block reward of the block with the given number:
+///
+/// - if the Paris hardfork is activated at the block according to the chain spec, no block rewards
+///   are given
+/// - otherwise, calculate block rewards with the block number
+fn base_block_reward<C: EthereumHardforks>(chain_spec: C, number: BlockNumber) -> Option<u128> {
+    if chain_spec.is_paris_active_at_block(number) {
+        return None
+    }
+    Some(base_block_reward_pre_merge(chain_spec, number))
+}
+
+/// Awaits all given futures like [`futures::future::try_join_all`], but with at most `limit` of
+/// them in progress at the same time.
+///
+/// The results are in the same order as the futures.
+async fn try_join_all_limited<F, T, E>(futures: Vec<F>, limit: usize) -> Result<Vec<T>, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let semaphore = Semaphore::new(limit.max(1));
+    futures::future::try_join_all(futures.into_iter().map(|fut| async {
+        let _permit = semaphore.acquire().await;
+        fut.await
+    }))
+    .await
+}
+
+/// Returns the number of entries on the page that skips the first `after` of `total` entries and
+/// includes at most `count` entries.
+fn paginated_count(total: u64, after: Option<u64>, count: Option<u64>) -> u64 {
+    total.saturating_sub(after.unwrap_or_default()).min(count.unwrap_or(u64::MAX))
+}
+
+/// Returns the single index of a `trace_get` request, `None` if no index is given.
+///
+/// Like the original implementation, more than a single index is rejected since `trace_get`
+/// returns a single trace.
+fn single_trace_index(indices: &[usize]) -> Result<Option<usize>, EthApiError> {
+    match indices {
+        [] => Ok(None),
+        [index] => Ok(Some(*index)),
+        _ => Err(EthApiError::InvalidParams(format!(
+            "trace_get supports a single index, got {} indices",
+            indices.len()
+        ))),
+    }
+}
+
+/// Returns the traces at the given indices, `None` for indices that are out of bounds.
+fn traces_at_indices(
+    traces: &[LocalizedTransactionTrace],
+    indices: &[usize],
+) -> Vec<Option<LocalizedTransactionTrace>> {
+    indices.iter().map(|index| traces.get(*index).cloned()).collect()
+}
+
+/// Awaits the given future, failing with [`EthApiError::ExecutionTimedOut`] if it doesn't
+/// complete within the given timeout.
+async fn with_timeout<T, E: From<EthApiError>>(
+    timeout: Option<Duration>,
+    fut: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, fut)
+            .await
+            .map_err(|_| EthApiError::ExecutionTimedOut(timeout))?,
+        None => fut.await,
+    }
+}
+
+/// Returns `true` if the state diff is the only requested trace type, which doesn't require the
+/// transaction to be traced.
+fn is_state_diff_only(trace_types: &HashSet<TraceType>) -> bool {
+    trace_types.len() == 1 && trace_types.contains(&TraceType::StateDiff)
+}
+
+/// Returns the [`TraceResults`] with only the state diff of the given execution result, populated
+/// from the pre-state in `db`.
+///
+/// This is equivalent to tracing the transaction and requesting only [`TraceType::StateDiff`].
+fn state_diff_trace_results<H, DB: DatabaseRef>(
+    res: &ResultAndState<H>,
+    db: DB,
+) -> Result<TraceResults, DB::Error> {
+    let mut state_diff = StateDiff::default();
+    populate_state_diff(&mut state_diff, db, res.state.iter())?;
+    Ok(TraceResults {
+        output: res.result.output().cloned().unwrap_or_default(),
+        trace: Vec::new(),
+        vm_trace: None,
+        state_diff: Some(state_diff),
+    })
+}
+
+/// Validates all code overrides in the given [`StateOverride`].
+///
+/// Overridden code must be non-empty, must not exceed the given maximum code size, see
+/// [`max_code_size`], and must be decodable, e.g. a code starting with the EIP-7702 magic bytes
+/// must be a valid delegation designator. The code size isn't checked if there's no maximum.
+fn validate_code_overrides(
+    state_overrides: &StateOverride,
+    max_code_size: Option<usize>,
+) -> Result<(), EthApiError> {
+    for (address, account_override) in state_overrides {
+        let Some(code) = &account_override.code else { continue };
+        if code.is_empty() {
+            return Err(EthApiError::InvalidParams(format!(
+                "invalid code override for {address}: code is empty"
+            )))
+        }
+        if let Some(max_code_size) = max_code_size.filter(|max| code.len() > *max) {
+            return Err(EthApiError::InvalidParams(format!(
+                "invalid code override for {address}: code size {} exceeds {max_code_size}",
+                code.len()
+            )))
+        }
+        Bytecode::new_raw_checked(code.clone())
+            .map_err(|err| EthApiError::InvalidBytecode(err.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Checks that the given gas limit of a call doesn't exceed the block gas limit.
+///
+/// A call without a gas limit is always accepted, its gas limit is capped by the block gas limit.
+fn check_block_gas_limit(gas: Option<u64>, block_gas_limit: u64) -> Result<(), EthApiError> {
+    match gas {
+        Some(gas) if gas > block_gas_limit => Err(EthApiError::InvalidParams(format!(
+            "call gas limit {gas} exceeds block gas limit {block_gas_limit}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Applies the state changes of a call of a [`TraceApi::trace_call_many_with_options`] batch and
+/// returns the requested account snapshots after the call.
+///
+/// The changes are committed to the given database if the next call depends on them. Independent
+/// calls leave the database untouched, their snapshots are taken from a database layered on top.
+fn apply_call_many_state<DB: DatabaseRef>(
+    db: &mut CacheDB<DB>,
+    state: EvmState,
+    options: &TraceCallManyOptions,
+    has_next: bool,
+) -> Result<Option<Vec<AccountSnapshot>>, DB::Error> {
+    if options.independent {
+        if options.snapshot_accounts.is_empty() {
+            return Ok(None)
+        }
+        let mut call_db = CacheDB::new(&*db);
+        call_db.commit(state);
+        return account_snapshots(&mut call_db, &options.snapshot_accounts).map(Some)
+    }
+
+    if !options.snapshot_accounts.is_empty() {
+        db.commit(state);
+        return account_snapshots(db, &options.snapshot_accounts).map(Some)
+    }
+    if has_next {
+        // need to apply the state changes of this call before executing the next call
+        db.commit(state)
+    }
+    Ok(None)
+}
+
+/// Returns the accounts whose state is reported before a call from `caller` to `kind`.
+///
+/// The recipient is only included if requested and the call isn't a contract creation.
+fn pre_state_accounts(caller: Address, kind: TxKind, include_recipient: bool) -> Vec<Address> {
+    let mut accounts = vec![caller];
+    if let Some(recipient) = kind.to().filter(|_| include_recipient) {
+        accounts.push(*recipient);
+    }
+    accounts
+}
+
+/// Sets the chain id of the given [`EvmEnv`] to the given override, if any.
+const fn override_chain_id<Spec>(evm_env: &mut EvmEnv<Spec>, chain_id: Option<u64>) {
+    if let Some(chain_id) = chain_id {
+        evm_env.cfg_env.chain_id = chain_id;
+    }
+}
+
+/// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
+/// beneficiary.
+fn reward_trace<H: BlockHeader>(header: &H, reward: RewardAction) -> LocalizedTransactionTrace {
+    LocalizedTransactionTrace {
+        block_hash: Some(header.hash_slow()),
+        block_number: Some(header.number()),
+        transaction_hash: None,
+        transaction_position: None,
+        trace: TransactionTrace {
+            trace_address: vec![],
+            subtraces: 0,
+            action: Action::Reward(reward),
+            error: None,
+            result: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes, TxKind};
+    use alloy_rpc_types_eth::state::AccountOverride;
+    use reth_chainspec::{Chain, ChainSpecBuilder, MAINNET, SEPOLIA};
+    use reth_evm::Evm;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_revm::db::EmptyDB;
+    use revm::{context::TxEnv, primitives::eip170::MAX_CODE_SIZE, state::AccountInfo};
+
+    fn code_override(code: Bytes) -> StateOverride {
+        let mut overrides = StateOverride::default();
+        overrides.insert(
+            address!("0x00000000000000000000000000000000000000aa"),
+            AccountOverride::default().with_code(code),
+        );
+        overrides
+    }
+
+    fn tx_trace(tx: u8, trace_address: Vec<usize>) -> LocalizedTransactionTrace {
+        LocalizedTransactionTrace {
+            block_hash: Some(B256::ZERO),
+            block_number: Some(1),
+            transaction_hash: Some(B256::with_last_byte(tx)),
+            transaction_position: Some(tx as u64),
+            ..test_utils::localized(test_utils::trace(test_utils::reward_action(), trace_address))
+        }
+    }
+
+    fn block_reward_trace() -> LocalizedTransactionTrace {
+        LocalizedTransactionTrace {
+            transaction_hash: None,
+            transaction_position: None,
+            ..tx_trace(0, vec![])
+        }
+    }
+
+    #[test]
+    fn base_block_reward_from_chain_spec() {
+        // Byzantium and Constantinople reduced the reward to 3 and 2 ether
+        let ether = 10u128.pow(18);
+        assert_eq!(base_block_reward(&*MAINNET, 15_537_393), Some(2 * ether));
+        assert_eq!(base_block_reward(&*MAINNET, 15_537_394), None);
+        assert_eq!(base_block_reward(&*SEPOLIA, 1_735_370), Some(2 * ether));
+        assert_eq!(base_block_reward(&*SEPOLIA, 1_735_371), None);
+
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .chain(Chain::from_id(1337))
+            .paris_at_ttd(U256::ZERO, 100)
+            .build();
+        assert_eq!(base_block_reward(&chain_spec, 99), Some(5 * ether));
+        assert_eq!(base_block_reward(&chain_spec, 100), None);
+    }
+
+    #[test]
+    fn counts_paginated_traces() {
+        assert_eq!(paginated_count(10, None, None), 10);
+        assert_eq!(paginated_count(10, Some(3), None), 7);
+        assert_eq!(paginated_count(10, Some(3), Some(5)), 5);
+        assert_eq!(paginated_count(10, Some(8), Some(5)), 2);
+        assert_eq!(paginated_count(10, Some(12), Some(5)), 0);
+        assert_eq!(paginated_count(10, None, Some(0)), 0);
+    }
+
+    #[test]
+    fn selects_traces_at_indices() {
+        let traces = vec![tx_trace(0, vec![]), tx_trace(0, vec![0]), tx_trace(0, vec![1])];
+        assert_eq!(
+            traces_at_indices(&traces, &[2, 0, 3, 2]),
+            vec![Some(traces[2].clone()), Some(traces[0].clone()), None, Some(traces[2].clone())]
+        );
+        assert!(traces_at_indices(&traces, &[]).is_empty());
+    }
+
+    #[test]
+    fn validates_trace_get_indices() {
+        assert_eq!(single_trace_index(&[]).unwrap(), None);
+        assert_eq!(single_trace_index(&[3]).unwrap(), Some(3));
+        let err = single_trace_index(&[0, 1]).unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+        assert_eq!(err.to_string(), "trace_get supports a single index, got 2 indices");
+
+        // a single index that is out of bounds doesn't match any trace
+        let traces = vec![tx_trace(0, vec![])];
+        assert_eq!(traces_at_indices(&traces, &[3]), vec![None]);
+    }
+
+    #[test]
+    fn paginates_by_trace() {
+        let traces = vec![
+            tx_trace(0, vec![]),
+            tx_trace(0, vec![0]),
+            tx_trace(1, vec![]),
+            block_reward_trace(),
+        ];
+
+        let page = TraceFilterPagination::Trace.paginate(traces.clone(), Some(1), Some(2));
+        assert_eq!(page, traces[1..3]);
+
+        let page = TraceFilterPagination::Trace.paginate(traces.clone(), None, None);
+        assert_eq!(page, traces);
+
+        let page = TraceFilterPagination::Trace.paginate(traces, Some(4), None);
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn paginates_by_transaction() {
+        let traces = vec![
+            tx_trace(0, vec![]),
+            tx_trace(0, vec![0]),
+            tx_trace(1, vec![]),
+            tx_trace(1, vec![0]),
+            tx_trace(1, vec![1]),
+            block_reward_trace(),
+            block_reward_trace(),
+        ];
+        let paginate = |after, count| {
+            TraceFilterPagination::Transaction.paginate(traces.clone(), after, count)
+        };
+
+        assert_eq!(paginate(None, None), traces);
+        assert_eq!(paginate(None, Some(1)), traces[..2]);
+        assert_eq!(paginate(Some(1), Some(1)), traces[2..5]);
+        assert_eq!(paginate(Some(1), None), traces[2..]);
+        assert_eq!(paginate(Some(2), Some(1)), traces[5..6]);
+        assert_eq!(paginate(Some(3), Some(5)), traces[6..]);
+        assert!(paginate(Some(4), None).is_empty());
+        assert!(paginate(None, Some(0)).is_empty());
+    }
+
+    #[test]
+    fn sorts_traces_by_gas_used() {
+        let with_gas_used = |tx, gas_used| LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                result: Some(TraceOutput::Call(CallOutput { gas_used, output: Bytes::new() })),
+                ..tx_trace(tx, vec![]).trace
+            },
+            ..tx_trace(tx, vec![])
+        };
+        let traces = vec![
+            with_gas_used(0, 100),
+            with_gas_used(1, 300),
+            block_reward_trace(),
+            with_gas_used(2, 100),
+            with_gas_used(3, 200),
+        ];
+
+        let mut sorted = traces.clone();
+        TraceFilterOrder::Block.sort(&mut sorted);
+        assert_eq!(sorted, traces);
+
+        TraceFilterOrder::GasUsed.sort(&mut sorted);
+        let expected =
+            vec![&traces[1], &traces[4], &traces[0], &traces[3], &traces[2]].into_iter().cloned();
+        assert_eq!(sorted, expected.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn numbers_block_traces_in_execution_order() {
+        let traces = vec![
+            vec![tx_trace(0, vec![]), tx_trace(0, vec![0]), tx_trace(0, vec![0, 0])],
+            vec![],
+            vec![tx_trace(2, vec![]), tx_trace(2, vec![0])],
+        ];
+
+        let frames = block_trace_frames(traces.clone(), true);
+        assert_eq!(
+            frames.iter().map(|frame| frame.exec_index).collect::<Vec<_>>(),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            frames.into_iter().map(|frame| frame.trace).collect::<Vec<_>>(),
+            traces.iter().flatten().cloned().collect::<Vec<_>>()
+        );
+
+        let frames = block_trace_frames(traces, false);
+        assert!(frames.iter().all(|frame| frame.exec_index.is_none()));
+    }
+
+    #[test]
+    fn selects_pre_state_accounts() {
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let recipient = address!("0x00000000000000000000000000000000000000bb");
+
+        assert_eq!(pre_state_accounts(caller, TxKind::Call(recipient), false), vec![caller]);
+        assert_eq!(
+            pre_state_accounts(caller, TxKind::Call(recipient), true),
+            vec![caller, recipient]
+        );
+        assert_eq!(pre_state_accounts(caller, TxKind::Create, true), vec![caller]);
+    }
+
+    #[test]
+    fn checks_block_gas_limit() {
+        assert!(check_block_gas_limit(None, 30_000_000).is_ok());
+        assert!(check_block_gas_limit(Some(30_000_000), 30_000_000).is_ok());
+        assert!(matches!(
+            check_block_gas_limit(Some(30_000_001), 30_000_000),
+            Err(EthApiError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn selects_gas_limit_overrides() {
+        let options = TraceCallManyOptions::default().with_gas_limits(vec![None, Some(50_000)]);
+        assert_eq!(options.gas_limit(0), None);
+        assert_eq!(options.gas_limit(1), Some(50_000));
+        assert_eq!(options.gas_limit(2), None);
+    }
+
+    #[test]
+    fn overrides_chain_id() {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        // returns the chain id
+        let code = bytes!("4660005260206000f3");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        let mut evm_env = EvmEnv::default();
+        override_chain_id(&mut evm_env, None);
+        assert_eq!(evm_env.cfg_env.chain_id, 1);
+        override_chain_id(&mut evm_env, Some(10));
+
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+        let mut evm =
+            EthEvmConfig::mainnet().evm_with_env_and_inspector(db, evm_env, &mut inspector);
+        let tx = TxEnv { kind: TxKind::Call(contract), gas_limit: 100_000, ..Default::default() };
+        let res = evm.transact(tx).unwrap();
+
+        let traces = inspector
+            .into_parity_builder()
+            .into_trace_results(&res.result, &HashSet::from_iter([TraceType::Trace]));
+        let output = traces.trace[0].result.as_ref().unwrap().output();
+        assert_eq!(U256::from_be_slice(output), U256::from(10));
+    }
+
+    #[test]
+    fn reports_forwarded_gas_at_entry() {
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let callee = address!("0x00000000000000000000000000000000000000bb");
+        // calls the callee with all remaining gas
+        let code = bytes!("600060006000600060007300000000000000000000000000000000000000bb5af100");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            caller,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+        db.insert_account_info(
+            callee,
+            AccountInfo { code: Some(Bytecode::new_raw(bytes!("00"))), ..Default::default() },
+        );
+
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+        let mut evm = EthEvmConfig::mainnet().evm_with_env_and_inspector(
+            db,
+            EvmEnv::default(),
+            &mut inspector,
+        );
+        let tx = TxEnv { kind: TxKind::Call(caller), gas_limit: 100_000, ..Default::default() };
+        assert!(evm.transact(tx).unwrap().result.is_success());
+
+        let traces = inspector.into_parity_builder().into_transaction_traces();
+        let gas = traces.iter().map(gas_at_entry).collect::<Vec<_>>();
+
+        // the intrinsic gas is deducted before the root frame is entered
+        let root = 100_000 - 21_000;
+        // five PUSH1, PUSH20 and GAS, then the cold account access of the CALL
+        let available = root - 5 * 3 - 3 - 2 - 2600;
+        assert_eq!(gas, vec![Some(root), Some(available - available / 64)]);
+    }
+
+    #[test]
+    fn matches_created_addresses() {
+        let created = address!("0x00000000000000000000000000000000000000cc");
+        let create = TransactionTrace {
+            action: Action::Create(Default::default()),
+            result: Some(TraceOutput::Create(CreateOutput {
+                address: created,
+                code: Bytes::new(),
+                gas_used: 0,
+            })),
+            error: None,
+            subtraces: 0,
+            trace_address: vec![],
+        };
+        let other_create = TransactionTrace {
+            result: Some(TraceOutput::Create(CreateOutput {
+                address: Address::ZERO,
+                code: Bytes::new(),
+                gas_used: 0,
+            })),
+            ..create.clone()
+        };
+        let call = tx_trace(0, vec![]).trace;
+
+        let any = HashSet::default();
+        assert!(matches_created_address(&any, &create));
+        assert!(matches_created_address(&any, &call));
+
+        let created_addresses = HashSet::from_iter([created]);
+        assert!(matches_created_address(&created_addresses, &create));
+        assert!(!matches_created_address(&created_addresses, &other_create));
+        assert!(!matches_created_address(&created_addresses, &call));
+    }
+
+    #[test]
+    fn restricts_tracing_to_allowlist() {
+        let allowed = address!("0x00000000000000000000000000000000000000aa");
+        let other = address!("0x00000000000000000000000000000000000000bb");
+        let allowlist = HashSet::from_iter([allowed]);
+
+        let unrestricted = TraceFilter::default();
+        let to_allowed = TraceFilter { to_address: vec![allowed], ..Default::default() };
+        let to_other = TraceFilter { to_address: vec![allowed, other], ..Default::default() };
+        assert!(check_allowed_filter(None, &unrestricted).is_ok());
+        assert!(check_allowed_filter(Some(&allowlist), &to_allowed).is_ok());
+        assert!(matches!(
+            check_allowed_filter(Some(&allowlist), &unrestricted),
+            Err(EthApiError::InvalidParams(_))
+        ));
+        let err = check_allowed_filter(Some(&allowlist), &to_other).unwrap_err();
+        assert_eq!(err.to_string(), format!("address {other} is not allowed to be traced"));
+
+        assert!(check_allowed_call_target(None, Some(TxKind::Create)).is_ok());
+        assert!(check_allowed_call_target(Some(&allowlist), Some(TxKind::Call(allowed))).is_ok());
+        assert!(check_allowed_call_target(Some(&allowlist), Some(TxKind::Call(other))).is_err());
+        assert!(check_allowed_call_target(Some(&allowlist), Some(TxKind::Create)).is_err());
+        assert!(check_allowed_call_target(Some(&allowlist), None).is_err());
+
+        let call = |from, to| TransactionTrace {
+            action: Action::Call(CallAction { from, to, ..Default::default() }),
+            result: None,
+            error: None,
+            subtraces: 0,
+            trace_address: vec![],
+        };
+        assert!(is_allowed_trace(None, &call(other, other)));
+        assert!(is_allowed_trace(Some(&allowlist), &call(allowed, other)));
+        assert!(is_allowed_trace(Some(&allowlist), &call(other, allowed)));
+        assert!(!is_allowed_trace(Some(&allowlist), &call(other, other)));
+    }
+
+    #[test]
+    fn matches_action_kinds() {
+        let call = |call_type| TransactionTrace {
+            action: Action::Call(CallAction { call_type, ..Default::default() }),
+            result: None,
+            error: None,
+            subtraces: 0,
+            trace_address: vec![],
+        };
+        let create =
+            TransactionTrace { action: Action::Create(Default::default()), ..call(CallType::Call) };
+        let static_call = call(CallType::StaticCall);
+        let delegate_call = call(CallType::DelegateCall);
+
+        assert_eq!(TraceActionKind::of(&call(CallType::Call).action), TraceActionKind::Call);
+        assert_eq!(TraceActionKind::of(&static_call.action), TraceActionKind::StaticCall);
+        assert_eq!(TraceActionKind::of(&create.action), TraceActionKind::Create);
+
+        let any = TraceFilterOptions::default();
+        assert!(any.matches(&create));
+        assert!(any.matches(&static_call));
+
+        let options = TraceFilterOptions::default().with_action_kinds(HashSet::from_iter([
+            TraceActionKind::Create,
+            TraceActionKind::StaticCall,
+        ]));
+        assert!(options.matches(&create));
+        assert!(options.matches(&static_call));
+        assert!(!options.matches(&delegate_call));
+        assert!(!options.matches(&call(CallType::Call)));
+    }
+
+    #[test]
+    fn validates_code_overrides() {
+        let max_code_size = Some(MAX_CODE_SIZE);
+
+        // no code override
+        validate_code_overrides(&StateOverride::default(), max_code_size).unwrap();
+
+        // valid code
+        validate_code_overrides(&code_override(bytes!("0x6080604052")), max_code_size).unwrap();
+
+        // empty code
+        let err = validate_code_overrides(&code_override(Bytes::new()), max_code_size).unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+
+        // oversized code
+        let oversized = Bytes::from(vec![0u8; MAX_CODE_SIZE + 1]);
+        let err =
+            validate_code_overrides(&code_override(oversized.clone()), max_code_size).unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+
+        // the code size is unlimited before spurious dragon
+        validate_code_overrides(&code_override(oversized), None).unwrap();
+
+        // malformed eip-7702 delegation designator
+        let err = validate_code_overrides(&code_override(bytes!("0xef0100aa")), max_code_size)
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidBytecode(_)));
+    }
+
+    #[test]
+    fn traces_transaction_converted_to_request() {
+        use alloy_consensus::{SignableTransaction, TxEip1559};
+        use alloy_primitives::Signature;
+        use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+        use reth_primitives_traits::Recovered;
+        use reth_rpc_convert::{transaction::TryIntoTxEnv, tx_to_request};
+
+        let sender = address!("0x00000000000000000000000000000000000000aa");
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        // stores the first calldata word and calls the sender with it
+        let code = bytes!(
+            "600035600055600060006020600060007300000000000000000000000000000000000000aa5af100"
+        );
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        let tx: TransactionSigned = TxEip1559 {
+            chain_id: 1,
+            gas_limit: 100_000,
+            to: TxKind::Call(contract),
+            input: bytes!("000000000000000000000000000000000000000000000000000000000000002a"),
+            ..Default::default()
+        }
+        .into_signed(Signature::test_signature())
+        .into();
+
+        let evm_config = EthEvmConfig::mainnet();
+        let evm_env = EvmEnv::default();
+        let trace = |tx_env| {
+            let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+            let mut evm =
+                evm_config.evm_with_env_and_inspector(db.clone(), evm_env.clone(), &mut inspector);
+            assert!(evm.transact(tx_env).unwrap().result.is_success());
+            drop(evm);
+            inspector.into_parity_builder().into_transaction_traces()
+        };
+
+        let mined = trace(evm_config.tx_env(Recovered::new_unchecked(&tx, sender)));
+        let request = tx_to_request::<EthPrimitives>(&tx, sender);
+        assert_eq!(request.from, Some(sender));
+        let converted =
+            trace(request.try_into_tx_env(&evm_env.cfg_env, &evm_env.block_env).unwrap());
+
+        assert_eq!(mined.len(), 2);
+        assert_eq!(converted, mined);
+    }
+
+    #[test]
+    fn state_diff_only_matches_full_trace() {
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        // stores the first calldata word and returns it
+        let code = bytes!("6000358060005560005260206000f3");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+        let tx_env = TxEnv {
+            caller,
+            kind: TxKind::Call(contract),
+            gas_limit: 100_000,
+            data: bytes!("000000000000000000000000000000000000000000000000000000000000002a"),
+            ..Default::default()
+        };
+
+        let evm_config = EthEvmConfig::mainnet();
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::all());
+        let res = evm_config
+            .evm_with_env_and_inspector(db.clone(), EvmEnv::default(), &mut inspector)
+            .transact(tx_env.clone())
+            .unwrap();
+        let all_types =
+            HashSet::from_iter([TraceType::Trace, TraceType::VmTrace, TraceType::StateDiff]);
+        let full = inspector
+            .into_parity_builder()
+            .into_trace_results_with_state(&res, &all_types, &db)
+            .unwrap();
+
+        let state_diff_only = HashSet::from_iter([TraceType::StateDiff]);
+        assert!(is_state_diff_only(&state_diff_only));
+        assert!(!is_state_diff_only(&all_types));
+        let res = evm_config.evm_with_env(db.clone(), EvmEnv::default()).transact(tx_env).unwrap();
+        let diff_only = state_diff_trace_results(&res, &db).unwrap();
+
+        assert!(diff_only.state_diff.as_ref().is_some_and(|diff| diff.0.contains_key(&contract)));
+        assert_eq!(diff_only.state_diff, full.state_diff);
+        assert_eq!(diff_only.output, full.output);
+        assert!(diff_only.trace.is_empty());
+        assert!(diff_only.vm_trace.is_none());
+    }
+
+    #[test]
+    fn narrows_trace_filter_blocks_by_index() {
+        struct Index(Vec<(BlockNumber, Address)>);
+
+        impl TraceIndexProvider for Index {
+            fn trace_block_numbers(
+                &self,
+                range: RangeInclusive<BlockNumber>,
+                from_addresses: &[Address],
+                to_addresses: &[Address],
+            ) -> reth_errors::ProviderResult<Vec<BlockNumber>> {
+                let mut numbers = self
+                    .0
+                    .iter()
+                    .filter(|(number, address)| {
+                        range.contains(number) &&
+                            (from_addresses.contains(address) || to_addresses.contains(address))
+                    })
+                    .map(|(number, _)| *number)
+                    .collect::<Vec<_>>();
+                numbers.dedup();
+                Ok(numbers)
+            }
+        }
+
+        let alice = address!("0x00000000000000000000000000000000000000aa");
+        let bob = address!("0x00000000000000000000000000000000000000bb");
+        let index = Index(vec![(5, alice), (1_000, bob), (1_000, alice), (50_000, alice)]);
+
+        // without addresses, the whole range is traced
+        let filter = TraceFilter::default();
+        assert_eq!(indexed_trace_filter_blocks(&index, &filter, 0..=100_000, 2).unwrap(), None);
+
+        let filter = TraceFilter { from_address: vec![bob], ..Default::default() };
+        assert_eq!(
+            indexed_trace_filter_blocks(&index, &filter, 0..=100_000, 2).unwrap(),
+            Some(vec![1_000])
+        );
+
+        // only the number of candidate blocks is limited, not the length of the range
+        let filter = TraceFilter { to_address: vec![alice], ..Default::default() };
+        assert_eq!(
+            indexed_trace_filter_blocks(&index, &filter, 100..=100_000, 2).unwrap(),
+            Some(vec![1_000, 50_000])
+        );
+        let err = indexed_trace_filter_blocks(&index, &filter, 0..=100_000, 2).unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+    }
+
+    #[test]
+    fn reports_configured_trace_filter_limit() {
+        assert!(check_trace_filter_range(0..=500, 500).is_ok());
+        let err = check_trace_filter_range(100..=701, 500).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Block range too large: 602 blocks requested, limited to 500 blocks"
+        );
+    }
+
+    #[test]
+    fn selects_replayed_transactions() {
+        assert_eq!(replay_selection(vec![3, 0, 3], 4).unwrap(), BTreeSet::from([0, 3]));
+        assert!(matches!(replay_selection(vec![], 4), Err(EthApiError::InvalidParams(_))));
+        let err = replay_selection(vec![1, 4], 4).unwrap_err();
+        assert_eq!(err.to_string(), "transaction index 4 out of range, block has 4 transactions");
+    }
+
+    #[test]
+    fn checks_call_tx_position() {
+        assert!(check_tx_position(0, 0).is_ok());
+        assert!(check_tx_position(2, 4).is_ok());
+        assert!(check_tx_position(4, 4).is_ok());
+        let err = check_tx_position(5, 4).unwrap_err();
+        assert_eq!(err.to_string(), "transaction index 5 out of range, block has 4 transactions");
+    }
+
+    #[test]
+    fn limits_traces_per_response() {
+        let traced = AtomicUsize::new(0);
+        assert!(count_traces(&traced, 2, Some(3)).is_ok());
+        assert!(count_traces(&traced, 1, Some(3)).is_ok());
+        let err = count_traces(&traced, 1, Some(3)).unwrap_err();
+        assert_eq!(err.to_string(), "Too many traces: response exceeds the limit of 3 traces");
+
+        assert!(count_traces(&AtomicUsize::new(0), usize::MAX, None).is_ok());
+
+        assert!(check_trace_limit(3, Some(3)).is_ok());
+        assert!(check_trace_limit(4, Some(3)).is_err());
+        assert!(check_trace_limit(usize::MAX, None).is_ok());
+    }
+
+    #[test]
+    fn rejects_oversized_call_many_batch() {
+        let calls = vec![(TransactionRequest::default(), HashSet::<TraceType>::default()); 3];
+        assert!(check_call_many_batch(calls.len(), Some(3)).is_ok());
+        let err = check_call_many_batch(calls.len(), Some(2)).unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+        assert_eq!(err.to_string(), "Too many calls: 3 calls requested, limited to 2 calls");
+
+        // batches are unlimited by default
+        assert!(check_call_many_batch(calls.len(), None).is_ok());
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_futures() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        let futures = (0..8)
+            .map(|i| {
+                let (running, max_running) = (&running, &max_running);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, ()>(i)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let results = try_join_all_limited(futures, 3).await.unwrap();
+        assert_eq!(results, (0..8).collect::<Vec<_>>());
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn times_out_trace_futures() {
+        let timeout = Duration::from_millis(10);
+        let err =
+            with_timeout(Some(timeout), std::future::pending::<Result<(), EthApiError>>()).await;
+        assert!(matches!(err, Err(EthApiError::ExecutionTimedOut(t)) if t == timeout));
+
+        let res = with_timeout(Some(timeout), async { Ok::<_, EthApiError>(1) }).await;
+        assert_eq!(res.unwrap(), 1);
+        let res = with_timeout(None, async { Ok::<_, EthApiError>(2) }).await;
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn drives_receiver_until_sender_done() {
+        let (sender, receiver) = mpsc::channel(1);
+        let driver = async move {
+            for i in 0..4 {
+                sender.send(i).await.unwrap();
+            }
+        };
+        let items = drive_receiver(receiver, driver).collect::<Vec<_>>().await;
+        assert_eq!(items, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn overrides_tracing_config() {
+        let trace_types = HashSet::from_iter([TraceType::Trace]);
+        let options = TraceCallOptions::default();
+        assert_eq!(
+            options.tracing_config(&trace_types),
+            TracingInspectorConfig::from_parity_config(&trace_types)
+        );
+
+        let config = TracingInspectorConfig::default_parity().set_memory_snapshots(true);
+        let options = options.with_inspector_config(config);
+        assert_eq!(options.tracing_config(&trace_types), config);
+        assert_eq!(options.tracing_config(&HashSet::from_iter([TraceType::VmTrace])), config);
+    }
+
+    #[test]
+    fn applies_independent_call_state() {
+        let account = address!("0x00000000000000000000000000000000000000aa");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(account, AccountInfo { nonce: 1, ..Default::default() });
+
+        let state = || {
+            let mut account_state =
+                revm::state::Account::from(AccountInfo { nonce: 2, ..Default::default() });
+            account_state.mark_touch();
+            EvmState::from_iter([(account, account_state)])
+        };
+        let nonce = |db: &CacheDB<EmptyDB>| db.basic_ref(account).unwrap().unwrap().nonce;
+
+        // independent calls leave the state untouched, but the snapshot includes the call
+        let options = TraceCallManyOptions::default()
+            .with_independent()
+            .with_snapshot_accounts(vec![account]);
+        let snapshot = apply_call_many_state(&mut db, state(), &options, true).unwrap().unwrap();
+        assert_eq!(snapshot[0].nonce, 2);
+        assert_eq!(nonce(&db), 1);
+        let options = TraceCallManyOptions::default().with_independent();
+        assert_eq!(apply_call_many_state(&mut db, state(), &options, true).unwrap(), None);
+        assert_eq!(nonce(&db), 1);
+
+        // dependent calls are applied for the next call
+        let options = TraceCallManyOptions::default();
+        assert_eq!(apply_call_many_state(&mut db, state(), &options, false).unwrap(), None);
+        assert_eq!(nonce(&db), 1);
+        assert_eq!(apply_call_many_state(&mut db, state(), &options, true).unwrap(), None);
+        assert_eq!(nonce(&db), 2);
+    }
+}
@@ -0,0 +1,128 @@
+//! Attribution of the opcode gas of a transaction to the executing contracts.
+
+use alloy_primitives::{map::HashMap, Address, B256};
+use alloy_rpc_types_trace::opcode::OpcodeGas;
+use revm::{bytecode::opcode::OpCode, interpreter::Interpreter, Inspector};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The opcode gas of a transaction, broken down by the contract executing the opcodes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOpcodeGasByAddress {
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The gas used per opcode for each executing contract, the opcodes are in no particular
+    /// order.
+    ///
+    /// For delegate calls the opcodes are attributed to the contract whose storage is used, i.e.
+    /// the caller.
+    pub opcode_gas: BTreeMap<Address, Vec<OpcodeGas>>,
+}
+
+/// An inspector that counts the executed opcodes and their gas usage, like
+/// [`OpcodeGasInspector`](revm_inspectors::opcode::OpcodeGasInspector), but separately for every
+/// executing contract.
+#[derive(Debug, Default)]
+pub(super) struct OpcodeGasByAddressInspector {
+    /// The count and gas used of each opcode per executing contract.
+    opcodes: HashMap<Address, HashMap<OpCode, (u64, u64)>>,
+    /// The executing contract, opcode and remaining gas of the current step.
+    last_step: Option<(Address, OpCode, u64)>,
+}
+
+impl OpcodeGasByAddressInspector {
+    /// Consumes the inspector and returns the opcode gas of the given transaction.
+    pub(super) fn into_transaction_opcode_gas(
+        self,
+        transaction_hash: B256,
+    ) -> TransactionOpcodeGasByAddress {
+        let opcode_gas = self
+            .opcodes
+            .into_iter()
+            .map(|(address, opcodes)| {
+                let opcode_gas = opcodes
+                    .into_iter()
+                    .map(|(opcode, (count, gas_used))| OpcodeGas {
+                        opcode: opcode.to_string(),
+                        count,
+                        gas_used,
+                    })
+                    .collect();
+                (address, opcode_gas)
+            })
+            .collect();
+        TransactionOpcodeGasByAddress { transaction_hash, opcode_gas }
+    }
+}
+
+impl<CTX> Inspector<CTX> for OpcodeGasByAddressInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut CTX) {
+        if let Some(opcode) = OpCode::new(interp.bytecode.opcode()) {
+            let address = interp.input.target_address;
+            self.opcodes.entry(address).or_default().entry(opcode).or_default().0 += 1;
+            self.last_step = Some((address, opcode, interp.gas.remaining()));
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut CTX) {
+        if let Some((address, opcode, gas_remaining)) = self.last_step.take() {
+            let gas_used = gas_remaining.saturating_sub(interp.gas.remaining());
+            if let Some(opcodes) = self.opcodes.get_mut(&address) {
+                opcodes.entry(opcode).or_default().1 += gas_used;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes, TxKind};
+    use reth_evm::{ConfigureEvm, Evm, EvmEnv};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::{bytecode::Bytecode, context::TxEnv, state::AccountInfo};
+
+    #[test]
+    fn attributes_opcode_gas_to_executing_contract() {
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let outer = address!("0x00000000000000000000000000000000000000cc");
+        let inner = address!("0x00000000000000000000000000000000000000bb");
+        // static calls the inner contract
+        let outer_code = bytes!("600060006000600073000000000000000000000000000000000000bb5afa00");
+        // computes 1 + 2
+        let inner_code = bytes!("600160020100");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            outer,
+            AccountInfo { code: Some(Bytecode::new_raw(outer_code)), ..Default::default() },
+        );
+        db.insert_account_info(
+            inner,
+            AccountInfo { code: Some(Bytecode::new_raw(inner_code)), ..Default::default() },
+        );
+        let tx_env =
+            TxEnv { caller, kind: TxKind::Call(outer), gas_limit: 100_000, ..Default::default() };
+
+        let mut inspector = OpcodeGasByAddressInspector::default();
+        EthEvmConfig::mainnet()
+            .evm_with_env_and_inspector(db, EvmEnv::default(), &mut inspector)
+            .transact(tx_env)
+            .unwrap();
+        let opcode_gas = inspector.into_transaction_opcode_gas(B256::ZERO).opcode_gas;
+
+        assert_eq!(opcode_gas.keys().copied().collect::<Vec<_>>(), vec![inner, outer]);
+        let find = |address: &Address, opcode: &str| {
+            opcode_gas[address].iter().find(|gas| gas.opcode == opcode).cloned()
+        };
+        assert_eq!(
+            find(&inner, "ADD"),
+            Some(OpcodeGas { opcode: "ADD".to_string(), count: 1, gas_used: 3 })
+        );
+        assert_eq!(find(&inner, "STATICCALL"), None);
+        assert_eq!(find(&outer, "PUSH1").map(|gas| gas.count), Some(4));
+        assert!(find(&outer, "STATICCALL").is_some());
+        assert_eq!(find(&outer, "ADD"), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! Aggregation of the opcode gas of a transaction by opcode category.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::opcode::OpcodeGas;
+use revm::bytecode::opcode::OpCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A group of related opcodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OpcodeCategory {
+    /// Arithmetic, e.g. `ADD`, `MUL` and `EXP`.
+    Arithmetic,
+    /// Comparison and bitwise logic, e.g. `LT`, `AND` and `SHL`.
+    Bitwise,
+    /// `KECCAK256`.
+    Hash,
+    /// Information about the executing account and the call, e.g. `CALLER`, `BALANCE` and
+    /// `CALLDATACOPY`.
+    Environment,
+    /// Information about the block, e.g. `NUMBER`, `BASEFEE` and `BLOBHASH`.
+    Block,
+    /// Stack manipulation: `POP`, `PUSH`, `DUP` and `SWAP`.
+    Stack,
+    /// Memory access: `MLOAD`, `MSTORE`, `MSTORE8`, `MSIZE` and `MCOPY`.
+    Memory,
+    /// Persistent storage access: `SLOAD` and `SSTORE`.
+    Storage,
+    /// Transient storage access: `TLOAD` and `TSTORE`.
+    TransientStorage,
+    /// Control flow and halting, e.g. `JUMP`, `RETURN`, `REVERT` and `SELFDESTRUCT`.
+    Control,
+    /// `LOG0` to `LOG4`.
+    Log,
+    /// Message calls: `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL`.
+    Call,
+    /// Contract creation: `CREATE` and `CREATE2`.
+    Create,
+    /// Any other opcode.
+    Other,
+}
+
+impl OpcodeCategory {
+    /// Returns the category of the given opcode.
+    pub const fn of(opcode: OpCode) -> Self {
+        match opcode.get() {
+            0x01..=0x0b => Self::Arithmetic,
+            0x10..=0x1e => Self::Bitwise,
+            0x20 => Self::Hash,
+            0x30..=0x3f => Self::Environment,
+            0x40..=0x4a => Self::Block,
+            0x50 | 0x5f..=0x9f => Self::Stack,
+            0x51..=0x53 | 0x59 | 0x5e => Self::Memory,
+            0x54 | 0x55 => Self::Storage,
+            0x5c | 0x5d => Self::TransientStorage,
+            0x00 | 0x56..=0x58 | 0x5a | 0x5b | 0xf3 | 0xfd..=0xff => Self::Control,
+            0xa0..=0xa4 => Self::Log,
+            0xf1 | 0xf2 | 0xf4 | 0xfa => Self::Call,
+            0xf0 | 0xf5 => Self::Create,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// The combined count and gas usage of all opcodes of an [`OpcodeCategory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcodeCategoryGas {
+    /// The category of the opcodes.
+    pub category: OpcodeCategory,
+    /// How many times opcodes of the category were executed.
+    pub count: u64,
+    /// Combined gas used by all executed opcodes of the category.
+    pub gas_used: u64,
+}
+
+/// The opcode gas of a transaction, along with the gas aggregated by [`OpcodeCategory`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOpcodeGasCategorized {
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The gas used per opcode, in no particular order.
+    pub opcode_gas: Vec<OpcodeGas>,
+    /// The gas used per category of the executed opcodes, ordered by category.
+    pub category_gas: Vec<OpcodeCategoryGas>,
+}
+
+impl TransactionOpcodeGasCategorized {
+    /// Creates the aggregated view from the count and gas used of each executed opcode.
+    pub(super) fn new(
+        transaction_hash: B256,
+        opcodes: impl IntoIterator<Item = (OpCode, (u64, u64))>,
+    ) -> Self {
+        let mut opcode_gas = Vec::new();
+        let mut categories = BTreeMap::<_, (u64, u64)>::new();
+        for (opcode, (count, gas_used)) in opcodes {
+            let category = categories.entry(OpcodeCategory::of(opcode)).or_default();
+            category.0 += count;
+            category.1 += gas_used;
+            opcode_gas.push(OpcodeGas { opcode: opcode.to_string(), count, gas_used });
+        }
+        let category_gas = categories
+            .into_iter()
+            .map(|(category, (count, gas_used))| OpcodeCategoryGas { category, count, gas_used })
+            .collect();
+        Self { transaction_hash, opcode_gas, category_gas }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categorizes_opcodes() {
+        assert_eq!(OpcodeCategory::of(OpCode::ADD), OpcodeCategory::Arithmetic);
+        assert_eq!(OpcodeCategory::of(OpCode::SAR), OpcodeCategory::Bitwise);
+        assert_eq!(OpcodeCategory::of(OpCode::KECCAK256), OpcodeCategory::Hash);
+        assert_eq!(OpcodeCategory::of(OpCode::CALLDATACOPY), OpcodeCategory::Environment);
+        assert_eq!(OpcodeCategory::of(OpCode::BLOBBASEFEE), OpcodeCategory::Block);
+        assert_eq!(OpcodeCategory::of(OpCode::PUSH0), OpcodeCategory::Stack);
+        assert_eq!(OpcodeCategory::of(OpCode::SWAP16), OpcodeCategory::Stack);
+        assert_eq!(OpcodeCategory::of(OpCode::MCOPY), OpcodeCategory::Memory);
+        assert_eq!(OpcodeCategory::of(OpCode::SLOAD), OpcodeCategory::Storage);
+        assert_eq!(OpcodeCategory::of(OpCode::SSTORE), OpcodeCategory::Storage);
+        assert_eq!(OpcodeCategory::of(OpCode::TSTORE), OpcodeCategory::TransientStorage);
+        assert_eq!(OpcodeCategory::of(OpCode::JUMPDEST), OpcodeCategory::Control);
+        assert_eq!(OpcodeCategory::of(OpCode::SELFDESTRUCT), OpcodeCategory::Control);
+        assert_eq!(OpcodeCategory::of(OpCode::LOG4), OpcodeCategory::Log);
+        assert_eq!(OpcodeCategory::of(OpCode::STATICCALL), OpcodeCategory::Call);
+        assert_eq!(OpcodeCategory::of(OpCode::CREATE2), OpcodeCategory::Create);
+    }
+
+    #[test]
+    fn aggregates_opcode_gas_by_category() {
+        let opcodes = [
+            (OpCode::PUSH1, (4, 12)),
+            (OpCode::SSTORE, (2, 20_100)),
+            (OpCode::SLOAD, (1, 2_100)),
+            (OpCode::DUP1, (1, 3)),
+        ];
+        let categorized = TransactionOpcodeGasCategorized::new(B256::ZERO, opcodes);
+
+        assert_eq!(categorized.opcode_gas.len(), 4);
+        assert_eq!(categorized.opcode_gas[1].opcode, "SSTORE");
+        assert_eq!(
+            categorized.category_gas,
+            vec![
+                OpcodeCategoryGas { category: OpcodeCategory::Stack, count: 5, gas_used: 15 },
+                OpcodeCategoryGas { category: OpcodeCategory::Storage, count: 3, gas_used: 22_200 },
+            ]
+        );
+    }
+}
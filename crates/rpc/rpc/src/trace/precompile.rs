@@ -0,0 +1,173 @@
+//! Annotation of calls to the well-known precompiles.
+
+use alloy_primitives::Address;
+use alloy_rpc_types_trace::parity::{Action, TransactionTrace};
+use serde::{Deserialize, Serialize};
+
+/// A well-known precompile of the Ethereum mainnet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Precompile {
+    /// `ECRECOVER` at `0x01`.
+    Ecrecover,
+    /// `SHA256` at `0x02`.
+    Sha256,
+    /// `RIPEMD160` at `0x03`.
+    Ripemd160,
+    /// `IDENTITY` at `0x04`.
+    Identity,
+    /// `MODEXP` at `0x05`.
+    Modexp,
+    /// `BN256ADD` at `0x06`.
+    Bn256Add,
+    /// `BN256MUL` at `0x07`.
+    Bn256Mul,
+    /// `BN256PAIRING` at `0x08`.
+    Bn256Pairing,
+    /// `BLAKE2F` at `0x09`.
+    Blake2F,
+    /// The KZG `POINT_EVALUATION` of EIP-4844 at `0x0a`.
+    PointEvaluation,
+    /// `BLS12_G1ADD` of EIP-2537 at `0x0b`.
+    Bls12G1Add,
+    /// `BLS12_G1MSM` of EIP-2537 at `0x0c`.
+    Bls12G1Msm,
+    /// `BLS12_G2ADD` of EIP-2537 at `0x0d`.
+    Bls12G2Add,
+    /// `BLS12_G2MSM` of EIP-2537 at `0x0e`.
+    Bls12G2Msm,
+    /// `BLS12_PAIRING_CHECK` of EIP-2537 at `0x0f`.
+    Bls12PairingCheck,
+    /// `BLS12_MAP_FP_TO_G1` of EIP-2537 at `0x10`.
+    Bls12MapFpToG1,
+    /// `BLS12_MAP_FP2_TO_G2` of EIP-2537 at `0x11`.
+    Bls12MapFp2ToG2,
+    /// `P256VERIFY` of EIP-7951 at `0x100`.
+    P256Verify,
+}
+
+impl Precompile {
+    /// Returns the precompile at the given address, if any.
+    ///
+    /// This only considers the address, regardless of whether the precompile is active at the
+    /// traced block.
+    pub fn from_address(address: Address) -> Option<Self> {
+        let (prefix, index) = address.0.split_at(18);
+        if prefix.iter().any(|byte| *byte != 0) {
+            return None
+        }
+        Some(match u16::from_be_bytes([index[0], index[1]]) {
+            0x01 => Self::Ecrecover,
+            0x02 => Self::Sha256,
+            0x03 => Self::Ripemd160,
+            0x04 => Self::Identity,
+            0x05 => Self::Modexp,
+            0x06 => Self::Bn256Add,
+            0x07 => Self::Bn256Mul,
+            0x08 => Self::Bn256Pairing,
+            0x09 => Self::Blake2F,
+            0x0a => Self::PointEvaluation,
+            0x0b => Self::Bls12G1Add,
+            0x0c => Self::Bls12G1Msm,
+            0x0d => Self::Bls12G2Add,
+            0x0e => Self::Bls12G2Msm,
+            0x0f => Self::Bls12PairingCheck,
+            0x10 => Self::Bls12MapFpToG1,
+            0x11 => Self::Bls12MapFp2ToG2,
+            0x100 => Self::P256Verify,
+            _ => return None,
+        })
+    }
+
+    /// Returns the precompile called by the given trace, if it's a call to a precompile.
+    pub fn of_trace(trace: &TransactionTrace) -> Option<Self> {
+        match &trace.action {
+            Action::Call(call) => Self::from_address(call.to),
+            _ => None,
+        }
+    }
+}
+
+/// A call to a well-known precompile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileCall {
+    /// The trace address of the call.
+    pub trace_address: Vec<usize>,
+    /// The called precompile.
+    pub precompile: Precompile,
+}
+
+/// Returns the calls of the given traces to well-known precompiles.
+pub(super) fn precompile_calls(traces: &[TransactionTrace]) -> Vec<PrecompileCall> {
+    traces
+        .iter()
+        .filter_map(|trace| {
+            Precompile::of_trace(trace).map(|precompile| PrecompileCall {
+                trace_address: trace.trace_address.clone(),
+                precompile,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::trace;
+    use alloy_primitives::address;
+    use alloy_rpc_types_trace::parity::{CallAction, CreateAction};
+
+    #[test]
+    fn resolves_precompiles_by_address() {
+        assert_eq!(
+            Precompile::from_address(Address::with_last_byte(0x01)),
+            Some(Precompile::Ecrecover)
+        );
+        assert_eq!(
+            Precompile::from_address(Address::with_last_byte(0x0a)),
+            Some(Precompile::PointEvaluation)
+        );
+        assert_eq!(
+            Precompile::from_address(address!("0x0000000000000000000000000000000000000100")),
+            Some(Precompile::P256Verify)
+        );
+        assert_eq!(Precompile::from_address(Address::ZERO), None);
+        assert_eq!(Precompile::from_address(Address::with_last_byte(0x12)), None);
+        assert_eq!(
+            Precompile::from_address(address!("0x0100000000000000000000000000000000000001")),
+            None
+        );
+    }
+
+    #[test]
+    fn annotates_precompile_calls() {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        let traces = vec![
+            trace(Action::Call(CallAction { to: contract, ..Default::default() }), vec![]),
+            trace(
+                Action::Call(CallAction {
+                    to: Address::with_last_byte(0x02),
+                    ..Default::default()
+                }),
+                vec![0],
+            ),
+            trace(Action::Create(CreateAction::default()), vec![1]),
+            trace(
+                Action::Call(CallAction {
+                    to: Address::with_last_byte(0x05),
+                    ..Default::default()
+                }),
+                vec![1, 0],
+            ),
+        ];
+
+        assert_eq!(
+            precompile_calls(&traces),
+            vec![
+                PrecompileCall { trace_address: vec![0], precompile: Precompile::Sha256 },
+                PrecompileCall { trace_address: vec![1, 0], precompile: Precompile::Modexp },
+            ]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Verification of traced transaction outcomes against the stored receipts of a block.
+
+use alloy_consensus::TxReceipt;
+use reth_primitives_traits::{receipts_gas_used, CumulativeGasError, NodePrimitives, ReceiptTy};
+
+/// The outcome of a traced transaction that is checked against its receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct TracedOutcome {
+    /// Whether the transaction succeeded.
+    pub(super) success: bool,
+    /// The gas used by the transaction.
+    pub(super) gas_used: u64,
+}
+
+/// A mismatch between the traced transactions of a block and the block's stored receipts.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TraceReceiptMismatch {
+    /// The number of traced transactions doesn't match the number of receipts.
+    #[error("traced {traced} transactions, but the block has {receipts} receipts")]
+    TransactionCount {
+        /// The number of traced transactions.
+        traced: usize,
+        /// The number of stored receipts.
+        receipts: usize,
+    },
+    /// The traced success of a transaction doesn't match the status of its receipt.
+    #[error("transaction {index}: traced success {traced}, but receipt status is {receipt}")]
+    Status {
+        /// The index of the transaction in the block.
+        index: usize,
+        /// Whether the traced transaction succeeded.
+        traced: bool,
+        /// The status of the receipt.
+        receipt: bool,
+    },
+    /// The traced gas used of a transaction doesn't match the gas used according to its receipt.
+    #[error("transaction {index}: traced gas used {traced}, but receipt gas used is {receipt}")]
+    GasUsed {
+        /// The index of the transaction in the block.
+        index: usize,
+        /// The traced gas used.
+        traced: u64,
+        /// The gas used according to the receipt.
+        receipt: u64,
+    },
+    /// The gas used of the transactions can't be derived from the receipts.
+    #[error(transparent)]
+    CumulativeGasUsed(#[from] CumulativeGasError),
+}
+
+/// Checks the traced outcome of each transaction of a block against its receipt.
+///
+/// The gas used by a transaction is the difference between the cumulative gas used of its receipt
+/// and the receipt of the previous transaction, see [`receipts_gas_used`].
+pub(super) fn verify_against_receipts<N: NodePrimitives>(
+    outcomes: &[TracedOutcome],
+    receipts: &[ReceiptTy<N>],
+) -> Result<(), TraceReceiptMismatch> {
+    if outcomes.len() != receipts.len() {
+        return Err(TraceReceiptMismatch::TransactionCount {
+            traced: outcomes.len(),
+            receipts: receipts.len(),
+        })
+    }
+
+    let gas_used = receipts_gas_used::<N>(receipts)?;
+    for (index, ((outcome, receipt), gas_used)) in
+        outcomes.iter().zip(receipts).zip(gas_used).enumerate()
+    {
+        if outcome.success != receipt.status() {
+            return Err(TraceReceiptMismatch::Status {
+                index,
+                traced: outcome.success,
+                receipt: receipt.status(),
+            })
+        }
+        if outcome.gas_used != gas_used {
+            return Err(TraceReceiptMismatch::GasUsed {
+                index,
+                traced: outcome.gas_used,
+                receipt: gas_used,
+            })
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxType;
+    use reth_ethereum_primitives::{EthPrimitives, Receipt};
+
+    fn receipt(success: bool, cumulative_gas_used: u64) -> Receipt {
+        Receipt { tx_type: TxType::Eip1559, success, cumulative_gas_used, logs: vec![] }
+    }
+
+    #[test]
+    fn detects_receipt_mismatch() {
+        let receipts = vec![receipt(true, 21_000), receipt(false, 71_000)];
+        let mut outcomes = vec![
+            TracedOutcome { success: true, gas_used: 21_000 },
+            TracedOutcome { success: false, gas_used: 50_000 },
+        ];
+        verify_against_receipts::<EthPrimitives>(&outcomes, &receipts).unwrap();
+
+        outcomes[1].gas_used = 49_000;
+        assert_eq!(
+            verify_against_receipts::<EthPrimitives>(&outcomes, &receipts),
+            Err(TraceReceiptMismatch::GasUsed { index: 1, traced: 49_000, receipt: 50_000 })
+        );
+
+        outcomes[1].success = true;
+        assert_eq!(
+            verify_against_receipts::<EthPrimitives>(&outcomes, &receipts),
+            Err(TraceReceiptMismatch::Status { index: 1, traced: true, receipt: false })
+        );
+
+        assert_eq!(
+            verify_against_receipts::<EthPrimitives>(&outcomes[..1], &receipts),
+            Err(TraceReceiptMismatch::TransactionCount { traced: 1, receipts: 2 })
+        );
+
+        // receipts with decreasing cumulative gas used are corrupt rather than mismatching
+        let receipts = vec![receipt(true, 21_000), receipt(false, 20_000)];
+        assert_eq!(
+            verify_against_receipts::<EthPrimitives>(&outcomes, &receipts),
+            Err(TraceReceiptMismatch::CumulativeGasUsed(CumulativeGasError {
+                index: 1,
+                cumulative_gas_used: 20_000,
+                previous: 21_000,
+            }))
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Decoding of the revert reasons of failed calls.
+
+use alloy_primitives::Bytes;
+use alloy_rpc_types_trace::parity::{LocalizedTransactionTrace, TraceOutput};
+use alloy_sol_types::{Panic, Revert, SolError};
+use serde::{Deserialize, Serialize};
+
+/// A trace of a call that failed, along with its decoded revert reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertedTrace {
+    /// The trace of the failed call.
+    #[serde(flatten)]
+    pub trace: LocalizedTransactionTrace,
+    /// The revert reason, if the output of the call is an ABI encoded `Error(string)` or
+    /// `Panic(uint256)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl RevertedTrace {
+    /// Returns the given trace with its decoded revert reason, or `None` if the trace didn't fail.
+    pub(super) fn new(trace: LocalizedTransactionTrace) -> Option<Self> {
+        trace.trace.error.as_ref()?;
+        let reason = match &trace.trace.result {
+            Some(TraceOutput::Call(output)) => revert_reason(&output.output),
+            Some(TraceOutput::Create(output)) => revert_reason(&output.code),
+            None => None,
+        };
+        Some(Self { trace, reason })
+    }
+}
+
+/// Decodes the given output of a reverted call if it's an `Error(string)` or `Panic(uint256)`.
+fn revert_reason(output: &Bytes) -> Option<String> {
+    if let Ok(revert) = Revert::abi_decode(output) {
+        return Some(revert.reason)
+    }
+    Panic::abi_decode(output).ok().map(|panic| panic.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::{call_trace, localized};
+    use alloy_primitives::U256;
+    use alloy_rpc_types_trace::parity::{CallOutput, TransactionTrace};
+
+    fn trace(error: Option<&str>, output: Bytes) -> LocalizedTransactionTrace {
+        LocalizedTransactionTrace {
+            transaction_position: Some(0),
+            block_number: Some(1),
+            ..localized(TransactionTrace {
+                error: error.map(ToString::to_string),
+                result: Some(TraceOutput::Call(CallOutput { gas_used: 0, output })),
+                ..call_trace(vec![0])
+            })
+        }
+    }
+
+    #[test]
+    fn decodes_revert_reasons() {
+        let revert = Revert { reason: "insufficient balance".to_string() }.abi_encode();
+        let reverted = RevertedTrace::new(trace(Some("Reverted"), revert.into())).unwrap();
+        assert_eq!(reverted.reason.as_deref(), Some("insufficient balance"));
+
+        let panic = Panic { code: U256::from(0x11) }.abi_encode();
+        let reverted = RevertedTrace::new(trace(Some("Reverted"), panic.into())).unwrap();
+        assert!(reverted.reason.is_some());
+
+        let reverted = RevertedTrace::new(trace(Some("Reverted"), Bytes::from_static(&[1, 2])));
+        assert_eq!(reverted.unwrap().reason, None);
+
+        assert!(RevertedTrace::new(trace(None, Bytes::new())).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+//! Block and uncle rewards reported by the reward traces.
+
+use super::base_block_reward;
+use alloy_evm::block::calc::{block_reward, ommer_reward};
+use alloy_primitives::BlockNumber;
+use reth_chainspec::EthereumHardforks;
+
+/// Supplies the block and uncle rewards of the reward traces, see
+/// [`TraceApi::register_block_rewards`](super::TraceApi::register_block_rewards).
+///
+/// The block and uncle rewards default to the Ethereum formula, chains with a different reward
+/// schedule only need to provide their base block reward.
+pub trait BlockRewardProvider: Send + Sync {
+    /// Returns the base block reward of the block with the given number, or `None` if the block
+    /// isn't rewarded, e.g. after the merge.
+    fn base_block_reward(&self, number: BlockNumber) -> Option<u128>;
+
+    /// Returns the reward of the block's beneficiary, given the base block reward and the number
+    /// of ommers of the block.
+    fn block_reward(&self, base_block_reward: u128, ommers: usize) -> u128 {
+        block_reward(base_block_reward, ommers)
+    }
+
+    /// Returns the reward of the beneficiary of an ommer with the given number, included in the
+    /// block with the given number.
+    fn ommer_reward(
+        &self,
+        base_block_reward: u128,
+        block_number: BlockNumber,
+        ommer_block_number: BlockNumber,
+    ) -> u128 {
+        ommer_reward(base_block_reward, block_number, ommer_block_number)
+    }
+}
+
+/// The Ethereum block rewards of the given chain spec, which are used unless a
+/// [`BlockRewardProvider`] is registered.
+#[derive(Debug, Clone)]
+pub struct EthereumBlockRewards<C> {
+    chain_spec: C,
+}
+
+impl<C> EthereumBlockRewards<C> {
+    /// Creates the block rewards of the given chain spec.
+    pub const fn new(chain_spec: C) -> Self {
+        Self { chain_spec }
+    }
+}
+
+impl<C: EthereumHardforks + Send + Sync> BlockRewardProvider for EthereumBlockRewards<C> {
+    fn base_block_reward(&self, number: BlockNumber) -> Option<u128> {
+        base_block_reward(&self.chain_spec, number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+
+    /// A chain that rewards every block with 1 ether and uncles with half the block reward.
+    struct FixedRewards;
+
+    impl BlockRewardProvider for FixedRewards {
+        fn base_block_reward(&self, _number: BlockNumber) -> Option<u128> {
+            Some(10u128.pow(18))
+        }
+
+        fn ommer_reward(&self, base_block_reward: u128, _: BlockNumber, _: BlockNumber) -> u128 {
+            base_block_reward / 2
+        }
+    }
+
+    #[test]
+    fn provides_block_rewards() {
+        let ether = 10u128.pow(18);
+        let ethereum = EthereumBlockRewards::new(MAINNET.clone());
+        assert_eq!(ethereum.base_block_reward(4_370_000), Some(3 * ether));
+        assert_eq!(ethereum.base_block_reward(15_537_394), None);
+        // a 32th of the base reward per ommer, an ommer one block back gets 7/8
+        assert_eq!(ethereum.block_reward(3 * ether, 1), 3 * ether + 3 * ether / 32);
+        assert_eq!(ethereum.ommer_reward(3 * ether, 100, 99), 3 * ether * 7 / 8);
+
+        let fixed = FixedRewards;
+        assert_eq!(fixed.base_block_reward(15_537_394), Some(ether));
+        assert_eq!(fixed.block_reward(ether, 2), ether + ether / 16);
+        assert_eq!(fixed.ommer_reward(ether, 100, 99), ether / 2);
+    }
+}
@@ -0,0 +1,78 @@
+//! Account snapshots taken between the calls of `trace_callMany`.
+
+use alloy_primitives::{Address, U256};
+use revm::Database;
+use serde::{Deserialize, Serialize};
+
+/// The balance and nonce of an account at a point of a sequence of calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshot {
+    /// The address of the account.
+    pub address: Address,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+}
+
+/// Returns the snapshots of the given accounts in the state of the database.
+///
+/// Accounts that don't exist are reported with zero balance and nonce.
+pub(super) fn account_snapshots<DB: Database>(
+    db: &mut DB,
+    addresses: &[Address],
+) -> Result<Vec<AccountSnapshot>, DB::Error> {
+    addresses
+        .iter()
+        .map(|address| {
+            let info = db.basic(*address)?.unwrap_or_default();
+            Ok(AccountSnapshot { address: *address, balance: info.balance, nonce: info.nonce })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_revm::db::{CacheDB, EmptyDB};
+    use revm::{
+        state::{Account, AccountInfo, AccountStatus},
+        DatabaseCommit,
+    };
+
+    #[test]
+    fn snapshots_committed_state() {
+        let sender = address!("0x00000000000000000000000000000000000000aa");
+        let unknown = address!("0x00000000000000000000000000000000000000bb");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            sender,
+            AccountInfo { balance: U256::from(100), nonce: 1, ..Default::default() },
+        );
+
+        let snapshots = account_snapshots(&mut db, &[sender, unknown]).unwrap();
+        assert_eq!(
+            snapshots,
+            vec![
+                AccountSnapshot { address: sender, balance: U256::from(100), nonce: 1 },
+                AccountSnapshot { address: unknown, balance: U256::ZERO, nonce: 0 },
+            ]
+        );
+
+        let account = Account {
+            info: AccountInfo { balance: U256::from(40), nonce: 2, ..Default::default() },
+            status: AccountStatus::Touched,
+            ..Default::default()
+        };
+        db.commit(std::iter::once((sender, account)).collect());
+
+        let snapshots = account_snapshots(&mut db, &[sender]).unwrap();
+        assert_eq!(
+            snapshots,
+            vec![AccountSnapshot { address: sender, balance: U256::from(40), nonce: 2 }]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Conversion of parity traces into `OpenTelemetry` spans.
+
+use alloy_primitives::map::HashMap;
+use alloy_rpc_types_trace::parity::{Action, CallType, LocalizedTransactionTrace};
+use opentelemetry::{
+    trace::{SpanContext, SpanId, SpanKind, Status, TraceFlags, TraceId, TraceState},
+    InstrumentationScope, KeyValue,
+};
+use opentelemetry_sdk::trace::{SpanData, SpanEvents, SpanLinks};
+use std::time::SystemTime;
+
+/// Converts the given traces into a tree of `OpenTelemetry` spans, one span per frame.
+///
+/// All frames of a transaction share a trace id that is derived from the transaction hash, the
+/// parent of a span is the frame one level above it by trace address. The span is named after the
+/// call type and carries the `from`, `to`, `gas`, `value` and `gasUsed` of the frame as attributes.
+///
+/// Reward traces don't belong to a transaction, each of them is a root span without a trace id.
+///
+/// Traces don't carry timing information, so the start and end time of all spans is the unix
+/// epoch.
+pub fn trace_to_spans(traces: &[LocalizedTransactionTrace]) -> Vec<SpanData> {
+    let scope = InstrumentationScope::builder("reth-rpc").build();
+    let span_id = |idx: usize| SpanId::from_bytes((idx as u64 + 1).to_be_bytes());
+    // reward traces all share an empty trace address, so they can't be parents
+    let span_ids = traces
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, trace)| {
+            let key = (trace.transaction_hash?, trace.trace.trace_address.as_slice());
+            Some((key, span_id(idx)))
+        })
+        .collect::<HashMap<_, _>>();
+
+    traces
+        .iter()
+        .enumerate()
+        .map(|(idx, trace)| {
+            let parent_span_id = trace
+                .transaction_hash
+                .zip(trace.trace.trace_address.split_last())
+                .and_then(|(hash, (_, parent))| span_ids.get(&(hash, parent)))
+                .copied()
+                .unwrap_or(SpanId::INVALID);
+            let trace_id = trace.transaction_hash.map_or(TraceId::INVALID, |hash| {
+                TraceId::from_bytes(hash[..16].try_into().expect("16 bytes"))
+            });
+
+            SpanData {
+                span_context: SpanContext::new(
+                    trace_id,
+                    span_id(idx),
+                    TraceFlags::SAMPLED,
+                    false,
+                    TraceState::default(),
+                ),
+                parent_span_id,
+                span_kind: SpanKind::Internal,
+                name: span_name(&trace.trace.action).into(),
+                start_time: SystemTime::UNIX_EPOCH,
+                end_time: SystemTime::UNIX_EPOCH,
+                attributes: span_attributes(trace),
+                dropped_attributes_count: 0,
+                events: SpanEvents::default(),
+                links: SpanLinks::default(),
+                status: trace.trace.error.clone().map_or(Status::Unset, Status::error),
+                instrumentation_scope: scope.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the name of the span of a frame with the given action.
+const fn span_name(action: &Action) -> &'static str {
+    match action {
+        Action::Call(call) => match call.call_type {
+            CallType::None | CallType::Call => "call",
+            CallType::CallCode => "callcode",
+            CallType::DelegateCall => "delegatecall",
+            CallType::StaticCall => "staticcall",
+            CallType::AuthCall => "authcall",
+        },
+        Action::Create(_) => "create",
+        Action::Selfdestruct(_) => "selfdestruct",
+        Action::Reward(_) => "reward",
+    }
+}
+
+/// Returns the attributes of the span of the given frame.
+fn span_attributes(trace: &LocalizedTransactionTrace) -> Vec<KeyValue> {
+    let mut attributes = Vec::with_capacity(5);
+    match &trace.trace.action {
+        Action::Call(call) => {
+            attributes.push(KeyValue::new("from", call.from.to_string()));
+            attributes.push(KeyValue::new("to", call.to.to_string()));
+            attributes.push(KeyValue::new("gas", call.gas as i64));
+            attributes.push(KeyValue::new("value", call.value.to_string()));
+        }
+        Action::Create(create) => {
+            attributes.push(KeyValue::new("from", create.from.to_string()));
+            if let Some(to) = trace.trace.result.as_ref().and_then(|res| res.created_contract()) {
+                attributes.push(KeyValue::new("to", to.to_string()));
+            }
+            attributes.push(KeyValue::new("gas", create.gas as i64));
+            attributes.push(KeyValue::new("value", create.value.to_string()));
+        }
+        Action::Selfdestruct(selfdestruct) => {
+            attributes.push(KeyValue::new("from", selfdestruct.address.to_string()));
+            attributes.push(KeyValue::new("to", selfdestruct.refund_address.to_string()));
+            attributes.push(KeyValue::new("value", selfdestruct.balance.to_string()));
+        }
+        Action::Reward(reward) => {
+            attributes.push(KeyValue::new("to", reward.author.to_string()));
+            attributes.push(KeyValue::new("value", reward.value.to_string()));
+        }
+    }
+    if let Some(result) = &trace.trace.result {
+        attributes.push(KeyValue::new("gasUsed", result.gas_used() as i64));
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::{call_trace, localized, reward_action, trace};
+    use alloy_primitives::{map::HashSet, B256};
+
+    fn frame(tx: u8, trace_address: Vec<usize>) -> LocalizedTransactionTrace {
+        LocalizedTransactionTrace {
+            transaction_hash: Some(B256::repeat_byte(tx)),
+            ..localized(call_trace(trace_address))
+        }
+    }
+
+    fn reward() -> LocalizedTransactionTrace {
+        localized(trace(reward_action(), vec![]))
+    }
+
+    #[test]
+    fn spans_follow_trace_tree() {
+        let traces = vec![
+            frame(1, vec![]),
+            frame(1, vec![0]),
+            frame(1, vec![0, 0]),
+            frame(1, vec![1]),
+            frame(2, vec![]),
+            frame(2, vec![0]),
+            reward(),
+            reward(),
+        ];
+        let spans = trace_to_spans(&traces);
+        let span_id = |idx: usize| spans[idx].span_context.span_id();
+
+        let parents = spans.iter().map(|span| span.parent_span_id).collect::<Vec<_>>();
+        assert_eq!(
+            parents,
+            vec![
+                SpanId::INVALID,
+                span_id(0),
+                span_id(1),
+                span_id(0),
+                SpanId::INVALID,
+                span_id(4),
+                SpanId::INVALID,
+                SpanId::INVALID
+            ]
+        );
+
+        // every frame has its own span, including the reward traces
+        let span_ids = spans.iter().map(|span| span.span_context.span_id()).collect::<HashSet<_>>();
+        assert_eq!(span_ids.len(), traces.len());
+
+        // frames of the same transaction share a trace id
+        let trace_id = |idx: usize| spans[idx].span_context.trace_id();
+        assert_eq!(trace_id(0), trace_id(3));
+        assert_ne!(trace_id(0), trace_id(4));
+        assert_eq!(trace_id(6), TraceId::INVALID);
+        assert!(spans[..6].iter().all(|span| span.name == "call"));
+        assert!(spans[6..].iter().all(|span| span.name == "reward"));
+    }
+}
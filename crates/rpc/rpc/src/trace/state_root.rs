@@ -0,0 +1,79 @@
+//! Verification of the post-state root of a traced block.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use reth_consensus::ConsensusError;
+use reth_errors::RethError;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::{GotExpected, NodePrimitives, RecoveredBlock};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_eth_types::EthApiError;
+use reth_storage_api::StateProvider;
+
+/// Executes the given block, including its system calls and withdrawals, on top of the state of
+/// its parent and checks that the resulting state root matches the state root of the header.
+///
+/// Returns [`ConsensusError::BodyStateRootDiff`] as internal error on mismatch.
+pub(super) fn verify_state_root<E, P>(
+    evm_config: &E,
+    state_provider: P,
+    block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+) -> Result<(), EthApiError>
+where
+    E: ConfigureEvm,
+    P: StateProvider,
+{
+    let output = evm_config
+        .batch_executor(StateProviderDatabase::new(&state_provider))
+        .execute(block)
+        .map_err(|err| EthApiError::Internal(err.into()))?;
+    let hashed_state = state_provider.hashed_post_state(&output.state);
+    let state_root = state_provider.state_root(hashed_state).map_err(EthApiError::from)?;
+    check_state_root(state_root, block.header().state_root())
+}
+
+fn check_state_root(got: B256, expected: B256) -> Result<(), EthApiError> {
+    if got != expected {
+        return Err(EthApiError::Internal(RethError::Consensus(ConsensusError::BodyStateRootDiff(
+            GotExpected { got, expected }.into(),
+        ))))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{constants::ETH_TO_WEI, Header};
+    use alloy_primitives::{address, keccak256, U256};
+    use reth_ethereum_primitives::{Block, BlockBody};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Account, Block as _};
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_trie_common::{root::state_root_unhashed, EMPTY_ROOT_HASH};
+
+    #[test]
+    fn verifies_state_root_of_block() {
+        let factory = create_test_provider_factory();
+        let beneficiary = address!("0x00000000000000000000000000000000000000bb");
+
+        // the only state change of an empty frontier block is the block reward
+        let reward = Account { balance: U256::from(5 * ETH_TO_WEI), ..Default::default() };
+        let state_root =
+            state_root_unhashed([(beneficiary, reward.into_trie_account(EMPTY_ROOT_HASH))]);
+        let header = Header { number: 1, beneficiary, state_root, ..Default::default() };
+        let block = Block { header, body: BlockBody::default() }.try_into_recovered().unwrap();
+
+        let evm_config = EthEvmConfig::mainnet();
+        verify_state_root(&evm_config, factory.latest().unwrap(), &block).unwrap();
+
+        let mut invalid = block.clone_sealed_block().unseal();
+        invalid.header.state_root = keccak256(state_root);
+        let invalid = invalid.try_into_recovered().unwrap();
+        let err = verify_state_root(&evm_config, factory.latest().unwrap(), &invalid).unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::Internal(RethError::Consensus(ConsensusError::BodyStateRootDiff(_)))
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+//! Storage only state diffs of `trace_call`.
+
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_trace::parity::{Delta, StateDiff};
+use revm::{state::EvmState, DatabaseRef};
+use revm_inspectors::tracing::parity::populate_state_diff;
+use std::collections::BTreeMap;
+
+/// The changed storage slots of a call, keyed by the address of the account and the slot.
+///
+/// Accounts without changed storage slots are omitted.
+pub type StorageDiff = BTreeMap<Address, BTreeMap<B256, Delta<B256>>>;
+
+/// Returns the [`StorageDiff`] of the given post-execution state.
+///
+/// The `state_diff` is projected down to the storage changes if it has already been populated,
+/// otherwise it is populated from the pre-state in `db` first.
+pub(super) fn storage_diff_of<DB: DatabaseRef>(
+    state_diff: Option<&StateDiff>,
+    state: &EvmState,
+    db: DB,
+) -> Result<StorageDiff, DB::Error> {
+    let populated;
+    let state_diff = match state_diff {
+        Some(state_diff) => state_diff,
+        None => {
+            let mut state_diff = StateDiff::default();
+            populate_state_diff(&mut state_diff, db, state.iter())?;
+            populated = state_diff;
+            &populated
+        }
+    };
+    Ok(project_storage(state_diff))
+}
+
+/// Keeps only the changed storage slots of the given [`StateDiff`].
+fn project_storage(state_diff: &StateDiff) -> StorageDiff {
+    state_diff
+        .0
+        .iter()
+        .filter_map(|(address, account)| {
+            let slots = account
+                .storage
+                .iter()
+                .filter(|(_, delta)| !matches!(delta, Delta::Unchanged))
+                .map(|(slot, delta)| (*slot, delta.clone()))
+                .collect::<BTreeMap<_, _>>();
+            (!slots.is_empty()).then_some((*address, slots))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, U256};
+    use alloy_rpc_types_trace::parity::{AccountDiff, ChangedType};
+
+    #[test]
+    fn projects_state_diff_to_storage() {
+        let contract = address!("0x00000000000000000000000000000000000000cc");
+        let caller = address!("0x00000000000000000000000000000000000000aa");
+        let slot = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let unchanged = b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+        let change = Delta::Changed(ChangedType { from: B256::ZERO, to: B256::with_last_byte(1) });
+
+        let mut state_diff = StateDiff::default();
+        state_diff.0.insert(
+            contract,
+            AccountDiff {
+                balance: Delta::Unchanged,
+                code: Delta::Unchanged,
+                nonce: Delta::Unchanged,
+                storage: BTreeMap::from([(slot, change.clone()), (unchanged, Delta::Unchanged)]),
+            },
+        );
+        state_diff.0.insert(
+            caller,
+            AccountDiff {
+                balance: Delta::Changed(ChangedType { from: U256::from(2), to: U256::from(1) }),
+                code: Delta::Unchanged,
+                nonce: Delta::Unchanged,
+                storage: BTreeMap::new(),
+            },
+        );
+
+        let storage_diff = project_storage(&state_diff);
+        assert_eq!(storage_diff, BTreeMap::from([(contract, BTreeMap::from([(slot, change)]))]));
+    }
+}
@@ -0,0 +1,36 @@
+//! Trace fixtures shared by the tests of the `trace` modules.
+
+use alloy_primitives::{Address, U256};
+use alloy_rpc_types_trace::parity::{
+    Action, CallAction, LocalizedTransactionTrace, RewardAction, RewardType, TransactionTrace,
+};
+
+/// Returns a successful trace of the given action without subcalls or output.
+pub(super) const fn trace(action: Action, trace_address: Vec<usize>) -> TransactionTrace {
+    TransactionTrace { action, error: None, result: None, subtraces: 0, trace_address }
+}
+
+/// Returns a successful trace of a default call without subcalls or output.
+pub(super) fn call_trace(trace_address: Vec<usize>) -> TransactionTrace {
+    trace(Action::Call(CallAction::default()), trace_address)
+}
+
+/// Returns the action of an empty block reward to the zero address.
+pub(super) const fn reward_action() -> Action {
+    Action::Reward(RewardAction {
+        author: Address::ZERO,
+        reward_type: RewardType::Block,
+        value: U256::ZERO,
+    })
+}
+
+/// Wraps the given trace into a [`LocalizedTransactionTrace`] without block or transaction info.
+pub(super) const fn localized(trace: TransactionTrace) -> LocalizedTransactionTrace {
+    LocalizedTransactionTrace {
+        trace,
+        block_hash: None,
+        block_number: None,
+        transaction_hash: None,
+        transaction_position: None,
+    }
+}
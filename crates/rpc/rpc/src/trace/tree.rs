@@ -0,0 +1,88 @@
+//! Nested call trees of the flat parity traces of a transaction.
+
+use alloy_rpc_types_trace::parity::LocalizedTransactionTrace;
+use serde::{Deserialize, Serialize};
+
+/// A trace of a transaction together with the traces of its subcalls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTreeNode {
+    /// The trace of the call.
+    #[serde(flatten)]
+    pub trace: LocalizedTransactionTrace,
+    /// The subcalls of the call, ordered by their trace address.
+    pub children: Vec<CallTreeNode>,
+}
+
+impl CallTreeNode {
+    /// Creates a node without any subcalls.
+    const fn new(trace: LocalizedTransactionTrace) -> Self {
+        Self { trace, children: Vec::new() }
+    }
+}
+
+/// Builds the call tree of the given flat traces of a transaction using their trace addresses.
+///
+/// Returns `None` if there are no traces. Traces whose parent is missing are attached to their
+/// closest present ancestor.
+pub(super) fn call_tree(mut traces: Vec<LocalizedTransactionTrace>) -> Option<CallTreeNode> {
+    // sorting by trace address visits parents before their children and siblings in order
+    traces.sort_by(|a, b| a.trace.trace_address.cmp(&b.trace.trace_address));
+    let mut traces = traces.into_iter();
+    let mut root = CallTreeNode::new(traces.next()?);
+    for trace in traces {
+        let depth = trace.trace.trace_address.len().saturating_sub(1);
+        let mut node = &mut root;
+        for index in &trace.trace.trace_address[..depth] {
+            let Some(pos) = node
+                .children
+                .iter()
+                .position(|child| child.trace.trace.trace_address.last() == Some(index))
+            else {
+                break
+            };
+            node = &mut node.children[pos];
+        }
+        node.children.push(CallTreeNode::new(trace));
+    }
+    Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_utils::{call_trace, localized};
+    use alloy_rpc_types_trace::parity::TransactionTrace;
+
+    fn trace(trace_address: Vec<usize>, subtraces: usize) -> LocalizedTransactionTrace {
+        localized(TransactionTrace { subtraces, ..call_trace(trace_address) })
+    }
+
+    fn addresses(node: &CallTreeNode) -> Vec<Vec<usize>> {
+        node.children.iter().map(|child| child.trace.trace.trace_address.clone()).collect()
+    }
+
+    #[test]
+    fn builds_call_tree() {
+        assert_eq!(call_tree(Vec::new()), None);
+
+        let traces = vec![
+            trace(vec![1], 0),
+            trace(vec![], 3),
+            trace(vec![0, 1], 0),
+            trace(vec![0], 2),
+            trace(vec![2], 0),
+            trace(vec![0, 0], 0),
+        ];
+        let root = call_tree(traces).unwrap();
+
+        assert!(root.trace.trace.trace_address.is_empty());
+        assert_eq!(addresses(&root), vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(addresses(&root.children[0]), vec![vec![0, 0], vec![0, 1]]);
+        assert!(root.children[1].children.is_empty());
+
+        let json = serde_json::to_value(&root).unwrap();
+        assert_eq!(json["traceAddress"], serde_json::json!([]));
+        assert_eq!(json["children"][0]["children"][1]["traceAddress"], serde_json::json!([0, 1]));
+    }
+}
@@ -100,6 +100,15 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// The block didn't remain canonical while it was read, because the canonical chain kept
+    /// changing.
+    #[error("block {block:?} was reorged while it was read, gave up after {attempts} attempts")]
+    ReorgInProgress {
+        /// The requested block.
+        block: BlockHashOrNumber,
+        /// The number of attempts made to read the block.
+        attempts: usize,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -779,10 +779,10 @@ mod tests {
     };
     use reth_static_file_types::StaticFileSegment;
     use reth_storage_api::{
-        BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
-        BlockReaderIdExt, BlockSource, ChangeSetReader, DatabaseProviderFactory, HeaderProvider,
-        ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory, TransactionVariant,
-        TransactionsProvider,
+        finalized_sealed_header, safe_sealed_header, BlockBodyIndicesProvider, BlockHashReader,
+        BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, ChangeSetReader,
+        DatabaseProviderFactory, HeaderProvider, ReceiptProvider, ReceiptProviderIdExt,
+        StateProviderFactory, TransactionVariant, TransactionsProvider,
     };
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
@@ -797,6 +797,33 @@ mod tests {
 
     const TEST_BLOCKS_COUNT: usize = 5;
 
+    #[test]
+    fn test_safe_and_finalized_sealed_header() -> eyre::Result<()> {
+        let factory = create_test_provider_factory();
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let block_1 = test_block_builder.generate_random_block(0, B256::ZERO);
+        let block_2 = test_block_builder.generate_random_block(1, block_1.hash());
+
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.insert_historical_block(block_1.clone())?;
+        provider_rw.insert_historical_block(block_2.clone())?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory)?;
+
+        // no safe or finalized block before the merge
+        assert_eq!(safe_sealed_header(&provider)?, None);
+        assert_eq!(finalized_sealed_header(&provider)?, None);
+
+        provider.set_safe(block_2.clone_sealed_header());
+        provider.set_finalized(block_1.clone_sealed_header());
+
+        assert_eq!(safe_sealed_header(&provider)?, Some(block_2.clone_sealed_header()));
+        assert_eq!(finalized_sealed_header(&provider)?, Some(block_1.clone_sealed_header()));
+
+        Ok(())
+    }
+
     const TEST_TRANSACTIONS_COUNT: u8 = 4;
 
     fn random_blocks(
@@ -3,11 +3,12 @@ use crate::{
     ReceiptProviderIdExt, TransactionVariant, TransactionsProvider,
 };
 use alloc::{sync::Arc, vec::Vec};
+use alloy_consensus::BlockHeader as _;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
 use core::ops::RangeInclusive;
-use reth_primitives_traits::{RecoveredBlock, SealedHeader};
-use reth_storage_errors::provider::ProviderResult;
+use reth_primitives_traits::{BlockTy, NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// A helper enum that represents the origin of the requested block.
 ///
@@ -392,3 +393,137 @@ pub trait ChainStateBlockWriter: Send + Sync {
     /// Saves the given safe block number in the DB.
     fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
 }
+
+/// Returns the canonical [`NodePrimitives`] block with the given hash or number, along with its
+/// senders.
+///
+/// The canonical chain can change while a block is read, so that the returned block is already
+/// replaced by the time it's used. To avoid this, the block is only returned if it's still the
+/// canonical block at its height after it has been read.
+///
+/// A block requested by number is read again if it was replaced, at most `max_attempts` times but
+/// at least once, before [`ProviderError::ReorgInProgress`] is returned. A block requested by hash
+/// is read once and `None` is returned if it isn't canonical.
+pub fn canonical_block<N, P>(
+    provider: &P,
+    id: BlockHashOrNumber,
+    max_attempts: usize,
+) -> ProviderResult<Option<RecoveredBlock<BlockTy<N>>>>
+where
+    N: NodePrimitives,
+    P: BlockReader<Block = BlockTy<N>>,
+{
+    read_canonical(
+        id,
+        max_attempts,
+        || provider.recovered_block(id, TransactionVariant::WithHash),
+        |number| provider.block_hash(number),
+    )
+}
+
+/// Reads a block with `read` until it matches the canonical hash at its height.
+fn read_canonical<B: reth_primitives_traits::Block>(
+    id: BlockHashOrNumber,
+    max_attempts: usize,
+    mut read: impl FnMut() -> ProviderResult<Option<RecoveredBlock<B>>>,
+    mut canonical_hash: impl FnMut(BlockNumber) -> ProviderResult<Option<B256>>,
+) -> ProviderResult<Option<RecoveredBlock<B>>> {
+    let mut read_canonical = || -> ProviderResult<_> {
+        let Some(block) = read()? else { return Ok(None) };
+        let is_canonical = canonical_hash(block.header().number())? == Some(block.hash());
+        Ok(Some((block, is_canonical)))
+    };
+
+    if id.as_hash().is_some() {
+        // the block with a given hash never changes, it's just not part of the canonical chain
+        return Ok(read_canonical()?.and_then(|(block, is_canonical)| is_canonical.then_some(block)))
+    }
+
+    let attempts = max_attempts.max(1);
+    for _ in 0..attempts {
+        match read_canonical()? {
+            None => return Ok(None),
+            Some((block, true)) => return Ok(Some(block)),
+            Some((_, false)) => {}
+        }
+    }
+    Err(ProviderError::ReorgInProgress { block: id, attempts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use reth_ethereum_primitives::Block;
+
+    fn block(extra_data: &'static [u8]) -> RecoveredBlock<Block> {
+        let block = Block {
+            header: alloy_consensus::Header {
+                number: 1,
+                extra_data: Bytes::from_static(extra_data),
+                ..Default::default()
+            },
+            body: Default::default(),
+        };
+        RecoveredBlock::new_unhashed(block, Vec::new())
+    }
+
+    #[test]
+    fn retries_block_replaced_while_read() {
+        let id = BlockHashOrNumber::Number(1);
+        let (old, new) = (block(b"old"), block(b"new"));
+        let new_hash = new.hash();
+
+        // the block is replaced between the first read and the canonical check
+        let mut reads = vec![new, old.clone()];
+        let block =
+            read_canonical(id, 3, || Ok(reads.pop()), |_| Ok(Some(new_hash))).unwrap().unwrap();
+        assert_eq!(block.hash(), new_hash);
+        assert!(reads.is_empty());
+
+        // the canonical chain keeps changing
+        let err = read_canonical(id, 3, || Ok(Some(old.clone())), |_| Ok(Some(new_hash)));
+        assert!(matches!(err, Err(ProviderError::ReorgInProgress { attempts: 3, .. })));
+
+        // the block is read at least once
+        let mut reads = 0;
+        let err = read_canonical(
+            id,
+            0,
+            || {
+                reads += 1;
+                Ok(Some(old.clone()))
+            },
+            |_| Ok(Some(new_hash)),
+        );
+        assert!(matches!(err, Err(ProviderError::ReorgInProgress { attempts: 1, .. })));
+        assert_eq!(reads, 1);
+
+        // unknown block
+        assert!(read_canonical::<Block>(id, 3, || Ok(None), |_| Ok(None)).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_non_canonical_block_by_hash() {
+        let (old, new) = (block(b"old"), block(b"new"));
+        let new_hash = new.hash();
+
+        let id = BlockHashOrNumber::Hash(old.hash());
+        let mut reads = 0;
+        let block = read_canonical(
+            id,
+            3,
+            || {
+                reads += 1;
+                Ok(Some(old.clone()))
+            },
+            |_| Ok(Some(new_hash)),
+        );
+        assert!(block.unwrap().is_none());
+        assert_eq!(reads, 1);
+
+        let id = BlockHashOrNumber::Hash(new_hash);
+        let block = read_canonical(id, 3, || Ok(Some(new.clone())), |_| Ok(Some(new_hash)));
+        assert_eq!(block.unwrap().unwrap().hash(), new_hash);
+    }
+}
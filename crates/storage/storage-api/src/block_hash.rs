@@ -1,6 +1,9 @@
-use alloc::vec::Vec;
+use crate::NodePrimitivesProvider;
+use alloc::{collections::BTreeMap, vec::Vec};
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{BlockNumber, B256};
+use core::ops::RangeInclusive;
+use reth_primitives_traits::NodePrimitives;
 use reth_storage_errors::provider::ProviderResult;
 
 /// Client trait for fetching block hashes by number.
@@ -33,3 +36,65 @@ pub trait BlockHashReader: Send + Sync {
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>>;
 }
+
+/// Returns the canonical block hashes of the [`NodePrimitives`] blocks in the given range, keyed
+/// by block number.
+///
+/// Blocks of the range that are not available are omitted.
+pub fn canonical_block_hashes<N, P>(
+    provider: &P,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<BTreeMap<BlockNumber, B256>>
+where
+    N: NodePrimitives,
+    P: BlockHashReader + NodePrimitivesProvider<Primitives = N>,
+{
+    let (start, end) = range.into_inner();
+    if start > end {
+        return Ok(BTreeMap::new())
+    }
+    let hashes = provider.canonical_hashes_range(start, end.saturating_add(1))?;
+    Ok((start..).zip(hashes).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_primitives::EthPrimitives;
+
+    /// Canonical chain of block hashes, starting at genesis.
+    struct CanonicalChain(Vec<B256>);
+
+    impl NodePrimitivesProvider for CanonicalChain {
+        type Primitives = EthPrimitives;
+    }
+
+    impl BlockHashReader for CanonicalChain {
+        fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+            Ok(self.0.get(number as usize).copied())
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            start: BlockNumber,
+            end: BlockNumber,
+        ) -> ProviderResult<Vec<B256>> {
+            Ok((start..end).map_while(|number| self.block_hash(number).unwrap()).collect())
+        }
+    }
+
+    #[test]
+    fn canonical_block_hashes_match_chain() {
+        let chain = CanonicalChain((0..10).map(B256::with_last_byte).collect());
+
+        let hashes = canonical_block_hashes(&chain, 3..=6).unwrap();
+        assert_eq!(hashes.len(), 4);
+        for (number, hash) in &hashes {
+            assert_eq!(chain.block_hash(*number).unwrap(), Some(*hash));
+        }
+
+        // blocks past the tip are omitted
+        let hashes = canonical_block_hashes(&chain, 8..=12).unwrap();
+        assert_eq!(hashes.keys().copied().collect::<Vec<_>>(), vec![8, 9]);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::BlockHashReader;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use crate::{BlockHashReader, HeaderProvider, NodePrimitivesProvider};
+use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
 use alloy_primitives::{BlockNumber, B256};
 use reth_chainspec::ChainInfo;
+use reth_primitives_traits::{NodePrimitives, SealedHeader, SealedHeaderFor};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// Client trait for getting important block numbers (such as the latest block number), converting
@@ -133,5 +134,39 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
     }
 }
 
+/// Returns the header of the current safe block of the [`NodePrimitives`] chain.
+///
+/// Returns `None` if no safe block is set, e.g. before the merge.
+pub fn safe_sealed_header<N, P>(provider: &P) -> ProviderResult<Option<SealedHeaderFor<N>>>
+where
+    N: NodePrimitives,
+    P: BlockIdReader
+        + HeaderProvider<Header = N::BlockHeader>
+        + NodePrimitivesProvider<Primitives = N>,
+{
+    sealed_header_for(provider, provider.safe_block_num_hash()?)
+}
+
+/// Returns the header of the current finalized block of the [`NodePrimitives`] chain.
+///
+/// Returns `None` if no finalized block is set, e.g. before the merge.
+pub fn finalized_sealed_header<N, P>(provider: &P) -> ProviderResult<Option<SealedHeaderFor<N>>>
+where
+    N: NodePrimitives,
+    P: BlockIdReader
+        + HeaderProvider<Header = N::BlockHeader>
+        + NodePrimitivesProvider<Primitives = N>,
+{
+    sealed_header_for(provider, provider.finalized_block_num_hash()?)
+}
+
+fn sealed_header_for<P: HeaderProvider>(
+    provider: &P,
+    num_hash: Option<BlockNumHash>,
+) -> ProviderResult<Option<SealedHeader<P::Header>>> {
+    let Some(BlockNumHash { hash, .. }) = num_hash else { return Ok(None) };
+    Ok(provider.header(&hash)?.map(|header| SealedHeader::new(header, hash)))
+}
+
 #[cfg(test)]
 fn _object_safe(_: Box<dyn BlockIdReader>) {}
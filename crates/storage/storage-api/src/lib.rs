@@ -45,12 +45,18 @@ pub use stage_checkpoint::*;
 mod state;
 pub use state::*;
 
+mod state_availability;
+pub use state_availability::*;
+
 mod storage;
 pub use storage::*;
 
 mod transactions;
 pub use transactions::*;
 
+mod trace_index;
+pub use trace_index::*;
+
 mod trie;
 pub use trie::*;
 
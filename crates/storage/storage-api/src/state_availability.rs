@@ -0,0 +1,82 @@
+use crate::{BlockNumReader, NodePrimitivesProvider};
+use alloy_primitives::BlockNumber;
+use core::marker::PhantomData;
+use reth_primitives_traits::NodePrimitives;
+use reth_prune_types::{PruneMode, PruneModes};
+use reth_storage_errors::provider::ProviderResult;
+
+/// The range of [`NodePrimitives`] blocks whose state is available, e.g. to trace or replay their
+/// transactions.
+///
+/// Historical state is only available for blocks whose account and storage history hasn't been
+/// pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateAvailability<N> {
+    /// The earliest block whose state is available.
+    pub earliest: BlockNumber,
+    /// The latest block whose state is available, i.e. the tip.
+    pub latest: BlockNumber,
+    _primitives: PhantomData<N>,
+}
+
+impl<N: NodePrimitives> StateAvailability<N> {
+    /// Returns the available state range for the given prune configuration and tip.
+    pub fn new(prune_modes: &PruneModes, tip: BlockNumber) -> Self {
+        let earliest = [prune_modes.account_history, prune_modes.storage_history]
+            .into_iter()
+            .flatten()
+            .map(|mode| match mode {
+                PruneMode::Full => tip,
+                PruneMode::Distance(distance) => tip.saturating_sub(distance),
+                PruneMode::Before(block) => block.min(tip),
+            })
+            .max()
+            .unwrap_or_default();
+        Self { earliest, latest: tip, _primitives: PhantomData }
+    }
+
+    /// Returns `true` if the state of the given block is available.
+    pub const fn contains(&self, block: BlockNumber) -> bool {
+        self.earliest <= block && block <= self.latest
+    }
+}
+
+/// Returns the range of blocks whose state is available on the given provider with the given
+/// prune configuration.
+pub fn state_availability<N, P>(
+    provider: &P,
+    prune_modes: &PruneModes,
+) -> ProviderResult<StateAvailability<N>>
+where
+    N: NodePrimitives,
+    P: BlockNumReader + NodePrimitivesProvider<Primitives = N>,
+{
+    Ok(StateAvailability::new(prune_modes, provider.best_block_number()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_primitives::EthPrimitives;
+
+    #[test]
+    fn reflects_pruning_boundary() {
+        let availability = StateAvailability::<EthPrimitives>::new(&PruneModes::none(), 100);
+        assert_eq!((availability.earliest, availability.latest), (0, 100));
+
+        let prune_modes = PruneModes {
+            account_history: Some(PruneMode::Distance(10)),
+            storage_history: Some(PruneMode::Before(80)),
+            ..PruneModes::none()
+        };
+        let availability = StateAvailability::<EthPrimitives>::new(&prune_modes, 100);
+        assert_eq!((availability.earliest, availability.latest), (90, 100));
+        assert!(!availability.contains(89));
+        assert!(availability.contains(90));
+        assert!(!availability.contains(101));
+
+        // only the state of the tip is kept
+        let availability = StateAvailability::<EthPrimitives>::new(&PruneModes::all(), 100);
+        assert_eq!((availability.earliest, availability.latest), (100, 100));
+    }
+}
@@ -0,0 +1,23 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for looking up the blocks that contain traces involving an address.
+///
+/// An index maps each address to the blocks with a parity trace that has the address as its
+/// `from` or `to` side, with the same sides that `trace_filter` matches on:
+///  - `from`: the caller of a call, the creator of a contract and the self-destructed contract
+///  - `to`: the callee of a call, the created contract, the refund address of a self-destruct and
+///    the author of a block or ommer reward
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait TraceIndexProvider: Send + Sync {
+    /// Returns the numbers of the blocks in the given range with a trace from one of
+    /// `from_addresses` or to one of `to_addresses`, in ascending order and without duplicates.
+    fn trace_block_numbers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        from_addresses: &[Address],
+        to_addresses: &[Address],
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
@@ -65,6 +65,14 @@ impl ExecutionPayload for CustomExecutionData {
     fn gas_used(&self) -> u64 {
         self.inner.gas_used()
     }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.inner.blob_gas_used()
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.inner.excess_blob_gas()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,7 +12,7 @@ pub use tx_type::*;
 pub mod tx_custom;
 pub use tx_custom::*;
 
-use reth_ethereum::primitives::NodePrimitives;
+use reth_ethereum::primitives::{NodePrimitives, PooledNodePrimitives};
 use reth_op::OpReceipt;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -21,7 +21,12 @@ pub struct CustomNodePrimitives;
 impl NodePrimitives for CustomNodePrimitives {
     type Block = Block;
     type BlockHeader = CustomHeader;
+    type OmmerHeader = CustomHeader;
     type BlockBody = BlockBody;
     type SignedTx = CustomTransaction;
     type Receipt = OpReceipt;
 }
+
+impl PooledNodePrimitives for CustomNodePrimitives {
+    type PooledTx = crate::pool::CustomPooledTransaction;
+}